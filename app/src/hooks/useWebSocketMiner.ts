@@ -45,7 +45,7 @@ interface MoveCallResult {
     txDigest: string
     txBytesBase64: string
     attempts: number
-    gasBudgetUsed: number
+    nonceValue: number
 }
 
 interface AddressMiningConfig {
@@ -58,7 +58,7 @@ interface PackageResult {
     txDigest: string
     txBytesBase64: string
     attempts: number
-    gasBudgetUsed: number
+    nonceValue: number
 }
 
 interface GasCoinResult {
@@ -67,7 +67,7 @@ interface GasCoinResult {
     txDigest: string
     txBytesBase64: string
     attempts: number
-    gasBudgetUsed: number
+    nonceValue: number
 }
 
 interface AddressResult {
@@ -182,7 +182,7 @@ export function useWebSocketMiner(): UseWebSocketMinerReturn {
                             txDigest: msg.tx_digest,
                             txBytesBase64: msg.tx_bytes_base64,
                             attempts: msg.attempts,
-                            gasBudgetUsed: msg.gas_budget_used,
+                            nonceValue: msg.nonce_value,
                         })
                         setIsRunning(false)
                         break
@@ -194,7 +194,7 @@ export function useWebSocketMiner(): UseWebSocketMinerReturn {
                             txDigest: msg.tx_digest,
                             txBytesBase64: msg.tx_bytes_base64,
                             attempts: msg.attempts,
-                            gasBudgetUsed: msg.gas_budget_used,
+                            nonceValue: msg.nonce_value,
                         })
                         setIsRunning(false)
                         break
@@ -216,7 +216,7 @@ export function useWebSocketMiner(): UseWebSocketMinerReturn {
                             txDigest: msg.tx_digest,
                             txBytesBase64: msg.tx_bytes_base64,
                             attempts: msg.attempts,
-                            gasBudgetUsed: msg.gas_budget_used,
+                            nonceValue: msg.nonce_value,
                         })
                         setIsRunning(false)
                         break
@@ -0,0 +1,127 @@
+//! BIP-39 mnemonic vanity addresses
+//!
+//! Samples fresh mnemonic entropy and derives the key along Sui's standard path, so the
+//! matching address can be recovered from the seed phrase in any Sui wallet.
+
+use crate::address::{FoundKey, KeyScheme};
+use crate::mining::search::SearchSpace;
+use crate::target::TargetChecker;
+
+use anyhow::Result;
+use bip39::{Language, Mnemonic, Seed};
+use rand::rngs::{OsRng, StdRng};
+use rand::{RngCore, SeedableRng};
+use sui_keys::key_derive::derive_key_pair_from_path;
+
+/// A mined seed phrase and the key derived from it
+#[derive(Clone, Debug)]
+pub struct FoundMnemonic {
+    pub phrase: String,
+    pub key: FoundKey,
+}
+
+/// Address search over random BIP-39 mnemonics
+#[derive(Clone, Debug)]
+pub struct MnemonicMode {
+    pub scheme: KeyScheme,
+    /// Number of mnemonic words (12 or 24)
+    pub words: usize,
+}
+
+impl MnemonicMode {
+    pub fn new(scheme: KeyScheme, words: usize) -> Result<Self> {
+        if words != 12 && words != 24 {
+            anyhow::bail!("Mnemonic length must be 12 or 24 words, got {}", words);
+        }
+        Ok(Self { scheme, words })
+    }
+
+    /// Entropy size in bytes: 128 bits for 12 words, 256 bits for 24
+    fn entropy_len(&self) -> usize {
+        self.words * 4 / 3
+    }
+
+    /// Derive the key for a phrase along the scheme's default path
+    pub fn derive(&self, mnemonic: &Mnemonic) -> Result<FoundKey> {
+        let seed = Seed::new(mnemonic, "");
+        let (address, keypair) =
+            derive_key_pair_from_path(seed.as_bytes(), None, &self.scheme.signature_scheme())
+                .map_err(|e| anyhow::anyhow!("Key derivation failed: {}", e))?;
+        // `to_bytes` is `flag || secret`
+        let secret = keypair.to_bytes()[1..].to_vec();
+        Ok(FoundKey::new(self.scheme, address.to_inner(), secret))
+    }
+}
+
+impl SearchSpace for MnemonicMode {
+    type Worker = StdRng;
+    type Found = FoundMnemonic;
+
+    fn worker(&self, _thread_index: usize) -> StdRng {
+        StdRng::from_rng(OsRng).expect("Failed to seed RNG from the OS")
+    }
+
+    fn try_candidate(&self, rng: &mut StdRng, target: &TargetChecker) -> Option<FoundMnemonic> {
+        let mut entropy = [0u8; 32];
+        let entropy = &mut entropy[..self.entropy_len()];
+        rng.fill_bytes(entropy);
+
+        let mnemonic = Mnemonic::from_entropy(entropy, Language::English).ok()?;
+        let key = self.derive(&mnemonic).ok()?;
+        target.matches(&key.address).then(|| FoundMnemonic {
+            phrase: mnemonic.phrase().to_string(),
+            key,
+        })
+    }
+
+    fn description(&self) -> &'static str {
+        "Mnemonic Address"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mnemonic_lengths() {
+        assert_eq!(
+            MnemonicMode::new(KeyScheme::Ed25519, 12)
+                .unwrap()
+                .entropy_len(),
+            16
+        );
+        assert_eq!(
+            MnemonicMode::new(KeyScheme::Ed25519, 24)
+                .unwrap()
+                .entropy_len(),
+            32
+        );
+        assert!(MnemonicMode::new(KeyScheme::Ed25519, 15).is_err());
+    }
+
+    #[test]
+    fn test_found_phrase_recovers_address() {
+        let target = TargetChecker::from_hex_prefix("c").unwrap();
+        for scheme in [
+            KeyScheme::Ed25519,
+            KeyScheme::Secp256k1,
+            KeyScheme::Secp256r1,
+        ] {
+            let mode = MnemonicMode::new(scheme, 12).unwrap();
+            let mut rng = StdRng::seed_from_u64(11);
+
+            let found = (0..1_000)
+                .find_map(|_| mode.try_candidate(&mut rng, &target))
+                .unwrap();
+            assert_eq!(found.phrase.split_whitespace().count(), 12);
+            assert!(hex::encode(found.key.address).starts_with('c'));
+            found.key.verify().unwrap();
+
+            // Recovering from the phrase alone gives the same address
+            let mnemonic = Mnemonic::from_phrase(&found.phrase, Language::English).unwrap();
+            let recovered = mode.derive(&mnemonic).unwrap();
+            assert_eq!(recovered.address, found.key.address);
+        }
+    }
+}
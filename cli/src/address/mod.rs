@@ -0,0 +1,301 @@
+//! Vanity Sui account address mining
+//!
+//! A Sui address is `Blake2b256(scheme flag || public key)`. Fresh keypairs are generated on
+//! every worker thread and the derived address is checked against the same `TargetChecker`
+//! patterns used for Object IDs.
+
+pub mod mnemonic;
+pub mod multisig;
+pub mod split;
+pub mod zklogin;
+
+use crate::mining::search::SearchSpace;
+use crate::target::TargetChecker;
+
+use anyhow::{Context, Result};
+use ed25519_dalek::SigningKey;
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use fastcrypto::secp256k1::Secp256k1KeyPair;
+use fastcrypto::secp256r1::Secp256r1KeyPair;
+use fastcrypto::traits::{KeyPair, ToFromBytes};
+use rand::SeedableRng;
+use rand::rngs::{OsRng, StdRng};
+use std::fmt;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, SignatureScheme, SuiKeyPair};
+
+/// Signature scheme of the mined keypair
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyScheme {
+    Ed25519,
+    Secp256k1,
+    Secp256r1,
+}
+
+impl KeyScheme {
+    /// Sui signature scheme flag prepended to the public key
+    pub fn flag(self) -> u8 {
+        self.signature_scheme().flag()
+    }
+
+    /// Standard Sui derivation path for the scheme (used for mnemonic keys)
+    pub fn derivation_path(self) -> &'static str {
+        match self {
+            Self::Ed25519 => "m/44'/784'/0'/0'/0'",
+            Self::Secp256k1 => "m/54'/784'/0'/0/0",
+            Self::Secp256r1 => "m/74'/784'/0'/0/0",
+        }
+    }
+
+    pub fn signature_scheme(self) -> SignatureScheme {
+        match self {
+            Self::Ed25519 => SignatureScheme::ED25519,
+            Self::Secp256k1 => SignatureScheme::Secp256k1,
+            Self::Secp256r1 => SignatureScheme::Secp256r1,
+        }
+    }
+}
+
+impl fmt::Display for KeyScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ed25519 => write!(f, "ed25519"),
+            Self::Secp256k1 => write!(f, "secp256k1"),
+            Self::Secp256r1 => write!(f, "secp256r1"),
+        }
+    }
+}
+
+/// Derive a Sui address from a scheme flag and public key bytes
+#[inline]
+pub fn derive_address(flag: u8, public_key: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b256::default();
+    hasher.update([flag]);
+    hasher.update(public_key);
+    hasher.finalize().digest
+}
+
+/// Generate a fresh keypair, returning `(public key, secret)` bytes
+///
+/// Secp public keys are in the 33-byte compressed form Sui hashes into addresses.
+pub fn generate_keypair(scheme: KeyScheme, rng: &mut StdRng) -> (Vec<u8>, Vec<u8>) {
+    fn split<K: KeyPair>(keypair: K) -> (Vec<u8>, Vec<u8>) {
+        (
+            keypair.public().as_bytes().to_vec(),
+            keypair.private().as_bytes().to_vec(),
+        )
+    }
+
+    match scheme {
+        KeyScheme::Ed25519 => {
+            let signing_key = SigningKey::generate(rng);
+            (
+                signing_key.verifying_key().as_bytes().to_vec(),
+                signing_key.to_bytes().to_vec(),
+            )
+        }
+        KeyScheme::Secp256k1 => split(Secp256k1KeyPair::generate(rng)),
+        KeyScheme::Secp256r1 => split(Secp256r1KeyPair::generate(rng)),
+    }
+}
+
+/// A mined keypair and its address
+#[derive(Clone)]
+pub struct FoundKey {
+    pub scheme: KeyScheme,
+    pub address: [u8; 32],
+    secret: Vec<u8>,
+}
+
+impl FoundKey {
+    pub fn new(scheme: KeyScheme, address: [u8; 32], secret: Vec<u8>) -> Self {
+        Self {
+            scheme,
+            address,
+            secret,
+        }
+    }
+
+    /// The keypair as a Sui keypair
+    ///
+    /// Built from the `flag || secret` bytes so it goes through `sui-types`' own fastcrypto.
+    pub fn keypair(&self) -> Result<SuiKeyPair> {
+        let mut bytes = Vec::with_capacity(1 + self.secret.len());
+        bytes.push(self.scheme.flag());
+        bytes.extend_from_slice(&self.secret);
+        SuiKeyPair::from_bytes(&bytes)
+            .map_err(|e| anyhow::anyhow!("Invalid {} private key: {}", self.scheme, e))
+    }
+
+    pub fn sui_address(&self) -> Result<SuiAddress> {
+        SuiAddress::from_bytes(self.address).context("Invalid address bytes")
+    }
+
+    /// Re-derive the address through `sui-types` to make sure the key really owns it
+    pub fn verify(&self) -> Result<()> {
+        let keypair = self.keypair()?;
+        let derived = SuiAddress::from(&keypair.public());
+        if derived != self.sui_address()? {
+            anyhow::bail!(
+                "Derived address mismatch: expected {}, got {}",
+                self.sui_address()?,
+                derived
+            );
+        }
+        Ok(())
+    }
+
+    /// Private key in Sui's Bech32 `suiprivkey1…` format (for `sui keytool import`)
+    pub fn bech32(&self) -> Result<String> {
+        self.keypair()?
+            .encode()
+            .map_err(|e| anyhow::anyhow!("Failed to encode private key: {}", e))
+    }
+
+    /// Private key in the base64 `flag || secret` format used by `sui.keystore`
+    pub fn keystore_base64(&self) -> Result<String> {
+        Ok(self.keypair()?.encode_base64())
+    }
+}
+
+impl fmt::Debug for FoundKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the secret
+        f.debug_struct("FoundKey")
+            .field("scheme", &self.scheme)
+            .field("address", &hex::encode(self.address))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Address search over random keypairs
+#[derive(Clone, Debug)]
+pub struct AddressMode {
+    pub scheme: KeyScheme,
+}
+
+impl AddressMode {
+    pub fn new(scheme: KeyScheme) -> Self {
+        Self { scheme }
+    }
+}
+
+impl AddressMode {
+    /// Check a fastcrypto keypair; secp public keys are hashed in compressed form
+    #[inline]
+    fn check_keypair<K: KeyPair>(&self, keypair: K, target: &TargetChecker) -> Option<FoundKey> {
+        let address = derive_address(self.scheme.flag(), keypair.public().as_bytes());
+        target
+            .matches(&address)
+            .then(|| FoundKey::new(self.scheme, address, keypair.private().as_bytes().to_vec()))
+    }
+}
+
+impl SearchSpace for AddressMode {
+    type Worker = StdRng;
+    type Found = FoundKey;
+
+    fn worker(&self, _thread_index: usize) -> StdRng {
+        StdRng::from_rng(OsRng).expect("Failed to seed RNG from the OS")
+    }
+
+    #[inline]
+    fn try_candidate(&self, rng: &mut StdRng, target: &TargetChecker) -> Option<FoundKey> {
+        match self.scheme {
+            KeyScheme::Ed25519 => {
+                let signing_key = SigningKey::generate(rng);
+                let address =
+                    derive_address(self.scheme.flag(), signing_key.verifying_key().as_bytes());
+                target
+                    .matches(&address)
+                    .then(|| FoundKey::new(self.scheme, address, signing_key.to_bytes().to_vec()))
+            }
+            KeyScheme::Secp256k1 => {
+                let keypair = Secp256k1KeyPair::generate(rng);
+                self.check_keypair(keypair, target)
+            }
+            KeyScheme::Secp256r1 => {
+                let keypair = Secp256r1KeyPair::generate(rng);
+                self.check_keypair(keypair, target)
+            }
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Account Address"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_address_matches_sui_types() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let signing_key = SigningKey::generate(&mut rng);
+        let address = derive_address(
+            KeyScheme::Ed25519.flag(),
+            signing_key.verifying_key().as_bytes(),
+        );
+
+        let found = FoundKey::new(KeyScheme::Ed25519, address, signing_key.to_bytes().to_vec());
+        found.verify().unwrap();
+    }
+
+    #[test]
+    fn test_address_mode_finds_prefix() {
+        let target = TargetChecker::from_hex_prefix("a").unwrap();
+        let mode = AddressMode::new(KeyScheme::Ed25519);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let found = (0..10_000)
+            .find_map(|_| mode.try_candidate(&mut rng, &target))
+            .unwrap();
+        assert!(hex::encode(found.address).starts_with('a'));
+        found.verify().unwrap();
+    }
+
+    #[test]
+    fn test_secp_schemes_verify() {
+        let target = TargetChecker::from_hex_prefix("b").unwrap();
+        for scheme in [KeyScheme::Secp256k1, KeyScheme::Secp256r1] {
+            let mode = AddressMode::new(scheme);
+            let mut rng = StdRng::seed_from_u64(5);
+
+            let found = (0..10_000)
+                .find_map(|_| mode.try_candidate(&mut rng, &target))
+                .unwrap();
+            assert!(hex::encode(found.address).starts_with('b'));
+            found.verify().unwrap();
+
+            let keystore = found.keystore_base64().unwrap();
+            let decoded = SuiKeyPair::decode_base64(&keystore).unwrap();
+            assert_eq!(decoded.public().flag(), scheme.flag());
+        }
+    }
+
+    #[test]
+    fn test_exported_key_round_trips() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let signing_key = SigningKey::generate(&mut rng);
+        let address = derive_address(0x00, signing_key.verifying_key().as_bytes());
+        let found = FoundKey::new(KeyScheme::Ed25519, address, signing_key.to_bytes().to_vec());
+
+        let bech32 = found.bech32().unwrap();
+        assert!(bech32.starts_with("suiprivkey1"));
+        let decoded = SuiKeyPair::decode(&bech32).unwrap();
+        assert_eq!(
+            SuiAddress::from(&decoded.public()),
+            found.sui_address().unwrap()
+        );
+
+        let keystore = found.keystore_base64().unwrap();
+        let decoded = SuiKeyPair::decode_base64(&keystore).unwrap();
+        assert_eq!(
+            SuiAddress::from(&decoded.public()),
+            found.sui_address().unwrap()
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Multisig vanity addresses
+//!
+//! A multisig address is `Blake2b256(0x03 || threshold as u16 LE || (flag || pubkey || weight)*)`.
+//! The team's member keys, weights and threshold are fixed; one extra "salt member" keypair is
+//! generated per candidate and appended until the address matches the target.
+
+use crate::address::{FoundKey, KeyScheme, derive_address, generate_keypair};
+use crate::mining::search::SearchSpace;
+use crate::target::TargetChecker;
+
+use anyhow::{Context, Result};
+use base64::{Engine as _, engine::general_purpose};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use rand::SeedableRng;
+use rand::rngs::{OsRng, StdRng};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey};
+use sui_types::multisig::MultiSigPublicKey;
+
+/// Address flag of multisig public keys
+const MULTISIG_FLAG: u8 = 0x03;
+
+/// Maximum number of members in a Sui multisig
+pub const MAX_MEMBERS: usize = 10;
+
+/// A fixed member of the multisig: `flag || public key` and its weight
+#[derive(Clone, Debug)]
+pub struct MultisigMember {
+    /// Sui base64 encoding of `flag || public key`, as used by `sui keytool`
+    pub public_key: String,
+    bytes: Vec<u8>,
+    pub weight: u8,
+}
+
+impl MultisigMember {
+    pub fn parse(public_key: &str, weight: u8) -> Result<Self> {
+        let bytes = general_purpose::STANDARD
+            .decode(public_key.trim())
+            .context("Member public key must be Sui base64 (flag || public key)")?;
+        // Validate flag and key length through sui-types
+        PublicKey::decode_base64(public_key.trim())
+            .map_err(|e| anyhow::anyhow!("Invalid member public key {}: {}", public_key, e))?;
+        if weight == 0 {
+            anyhow::bail!("Member weights must be at least 1");
+        }
+        Ok(Self {
+            public_key: public_key.trim().to_string(),
+            bytes,
+            weight,
+        })
+    }
+}
+
+/// A mined salt member and the resulting multisig
+#[derive(Clone, Debug)]
+pub struct FoundMultisig {
+    pub address: [u8; 32],
+    pub salt_key: FoundKey,
+    /// Sui base64 `flag || public key` of the salt member
+    pub salt_public_key: String,
+}
+
+/// Address search over fresh salt-member keys for a fixed multisig
+#[derive(Clone, Debug)]
+pub struct MultisigMode {
+    pub members: Vec<MultisigMember>,
+    pub threshold: u16,
+    /// Scheme of the generated salt member
+    pub scheme: KeyScheme,
+    pub salt_weight: u8,
+    /// `0x03 || threshold || fixed members`, shared by every candidate
+    preimage: Vec<u8>,
+}
+
+impl MultisigMode {
+    pub fn new(
+        members: Vec<MultisigMember>,
+        threshold: u16,
+        scheme: KeyScheme,
+        salt_weight: u8,
+    ) -> Result<Self> {
+        if members.is_empty() {
+            anyhow::bail!("At least one member public key is required");
+        }
+        if members.len() + 1 > MAX_MEMBERS {
+            anyhow::bail!(
+                "A multisig has at most {} members, including the salt member",
+                MAX_MEMBERS
+            );
+        }
+        if salt_weight == 0 {
+            anyhow::bail!("Salt member weight must be at least 1");
+        }
+        for (i, member) in members.iter().enumerate() {
+            if members[..i].iter().any(|m| m.bytes == member.bytes) {
+                anyhow::bail!("Duplicate member public key {}", member.public_key);
+            }
+        }
+
+        let total: u32 = members.iter().map(|m| m.weight as u32).sum::<u32>() + salt_weight as u32;
+        if threshold == 0 || threshold as u32 > total {
+            anyhow::bail!(
+                "Threshold must be between 1 and the total weight {}, got {}",
+                total,
+                threshold
+            );
+        }
+
+        let mut preimage = vec![MULTISIG_FLAG];
+        preimage.extend_from_slice(&threshold.to_le_bytes());
+        for member in &members {
+            preimage.extend_from_slice(&member.bytes);
+            preimage.push(member.weight);
+        }
+
+        Ok(Self {
+            members,
+            threshold,
+            scheme,
+            salt_weight,
+            preimage,
+        })
+    }
+
+    /// Whether the fixed members alone can't reach the threshold (the salt key must sign)
+    pub fn salt_required(&self) -> bool {
+        let fixed: u32 = self.members.iter().map(|m| m.weight as u32).sum();
+        fixed < self.threshold as u32
+    }
+
+    #[inline]
+    fn address_with_salt(&self, salt_public_key: &[u8]) -> [u8; 32] {
+        let mut hasher = Blake2b256::default();
+        hasher.update(&self.preimage);
+        hasher.update([self.scheme.flag()]);
+        hasher.update(salt_public_key);
+        hasher.update([self.salt_weight]);
+        hasher.finalize().digest
+    }
+
+    /// Re-derive the multisig address through `sui-types`
+    pub fn verify(&self, found: &FoundMultisig) -> Result<SuiAddress> {
+        let pks = self
+            .members
+            .iter()
+            .map(|m| m.public_key.as_str())
+            .chain(std::iter::once(found.salt_public_key.as_str()))
+            .map(|pk| PublicKey::decode_base64(pk).map_err(|e| anyhow::anyhow!("{}", e)))
+            .collect::<Result<Vec<_>>>()?;
+        let weights = self
+            .members
+            .iter()
+            .map(|m| m.weight)
+            .chain(std::iter::once(self.salt_weight))
+            .collect();
+
+        let multisig = MultiSigPublicKey::new(pks, weights, self.threshold)
+            .map_err(|e| anyhow::anyhow!("Invalid multisig: {}", e))?;
+        let derived = SuiAddress::from(&multisig);
+        let expected = SuiAddress::from_bytes(found.address).context("Invalid address bytes")?;
+        if derived != expected {
+            anyhow::bail!(
+                "Derived multisig address mismatch: expected {}, got {}",
+                expected,
+                derived
+            );
+        }
+        Ok(derived)
+    }
+
+    /// Arguments for `sui keytool multi-sig-address`, in member order
+    pub fn keytool_args(&self, found: &FoundMultisig) -> String {
+        let pks: Vec<&str> = self
+            .members
+            .iter()
+            .map(|m| m.public_key.as_str())
+            .chain(std::iter::once(found.salt_public_key.as_str()))
+            .collect();
+        let weights: Vec<String> = self
+            .members
+            .iter()
+            .map(|m| m.weight)
+            .chain(std::iter::once(self.salt_weight))
+            .map(|w| w.to_string())
+            .collect();
+        format!(
+            "--pks {} --weights {} --threshold {}",
+            pks.join(" "),
+            weights.join(" "),
+            self.threshold
+        )
+    }
+}
+
+impl SearchSpace for MultisigMode {
+    type Worker = StdRng;
+    type Found = FoundMultisig;
+
+    fn worker(&self, _thread_index: usize) -> StdRng {
+        StdRng::from_rng(OsRng).expect("Failed to seed RNG from the OS")
+    }
+
+    #[inline]
+    fn try_candidate(&self, rng: &mut StdRng, target: &TargetChecker) -> Option<FoundMultisig> {
+        let (public_key, secret) = generate_keypair(self.scheme, rng);
+        let address = self.address_with_salt(&public_key);
+        if !target.matches(&address) {
+            return None;
+        }
+
+        let mut flagged = vec![self.scheme.flag()];
+        flagged.extend_from_slice(&public_key);
+        Some(FoundMultisig {
+            address,
+            salt_key: FoundKey::new(
+                self.scheme,
+                derive_address(self.scheme.flag(), &public_key),
+                secret,
+            ),
+            salt_public_key: general_purpose::STANDARD.encode(flagged),
+        })
+    }
+
+    fn description(&self) -> &'static str {
+        "Multisig Address"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(scheme: KeyScheme, seed: u64, weight: u8) -> MultisigMember {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (public_key, _) = generate_keypair(scheme, &mut rng);
+        let mut flagged = vec![scheme.flag()];
+        flagged.extend_from_slice(&public_key);
+        MultisigMember::parse(&general_purpose::STANDARD.encode(flagged), weight).unwrap()
+    }
+
+    #[test]
+    fn test_multisig_address_matches_sui_types() {
+        let members = vec![
+            member(KeyScheme::Ed25519, 1, 1),
+            member(KeyScheme::Secp256k1, 2, 2),
+        ];
+        let mode = MultisigMode::new(members, 2, KeyScheme::Ed25519, 1).unwrap();
+        assert!(!mode.salt_required());
+
+        let target = TargetChecker::from_hex_prefix("e").unwrap();
+        let mut rng = StdRng::seed_from_u64(9);
+        let found = (0..10_000)
+            .find_map(|_| mode.try_candidate(&mut rng, &target))
+            .unwrap();
+
+        let address = mode.verify(&found).unwrap();
+        assert!(address.to_string().starts_with("0xe"));
+        found.salt_key.verify().unwrap();
+        assert!(
+            mode.keytool_args(&found)
+                .ends_with("--weights 1 2 1 --threshold 2")
+        );
+    }
+
+    #[test]
+    fn test_multisig_validation() {
+        let a = member(KeyScheme::Ed25519, 1, 1);
+        let b = member(KeyScheme::Ed25519, 2, 1);
+
+        // Threshold above the total weight (1 + 1 + salt 1)
+        assert!(MultisigMode::new(vec![a.clone(), b.clone()], 4, KeyScheme::Ed25519, 1).is_err());
+        assert!(MultisigMode::new(vec![a.clone(), a.clone()], 1, KeyScheme::Ed25519, 1).is_err());
+        assert!(MultisigMode::new(vec![a.clone()], 1, KeyScheme::Ed25519, 0).is_err());
+
+        let mode = MultisigMode::new(vec![a, b], 3, KeyScheme::Ed25519, 1).unwrap();
+        assert!(mode.salt_required());
+    }
+}
@@ -0,0 +1,375 @@
+//! Split-key (blinded) vanity addresses
+//!
+//! The user keeps a secret key `s` and hands out only its public key `P = s·G`. The miner
+//! searches an offset `k` such that the address of `P + k·G` matches the target, without
+//! ever learning `s`. The user then combines `s + k` locally to get the vanity private key.
+//!
+//! Only secp256k1 and secp256r1 support this: Ed25519 secret keys are hashed seeds, so an
+//! offset scalar can't be turned back into an importable Ed25519 key.
+
+use crate::address::{FoundKey, KeyScheme, derive_address};
+use crate::mining::search::SearchSpace;
+use crate::target::TargetChecker;
+
+use anyhow::{Context, Result};
+use base64::{Engine as _, engine::general_purpose};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::{Field, PrimeField};
+use rand::SeedableRng;
+use rand::rngs::{OsRng, StdRng};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::SuiKeyPair;
+
+/// Size of a compressed secp public key
+const COMPRESSED_LEN: usize = 33;
+
+/// A point on one of the supported curves
+#[derive(Clone, Copy, Debug)]
+enum CurvePoint {
+    K1(k256::ProjectivePoint),
+    R1(p256::ProjectivePoint),
+}
+
+impl CurvePoint {
+    fn decompress(scheme: KeyScheme, bytes: &[u8]) -> Result<Self> {
+        match scheme {
+            KeyScheme::Secp256k1 => {
+                let key = k256::PublicKey::from_sec1_bytes(bytes)
+                    .map_err(|_| anyhow::anyhow!("Invalid secp256k1 public key"))?;
+                Ok(Self::K1(key.to_projective()))
+            }
+            KeyScheme::Secp256r1 => {
+                let key = p256::PublicKey::from_sec1_bytes(bytes)
+                    .map_err(|_| anyhow::anyhow!("Invalid secp256r1 public key"))?;
+                Ok(Self::R1(key.to_projective()))
+            }
+            KeyScheme::Ed25519 => anyhow::bail!(
+                "Split-key mining is not supported for ed25519, use secp256k1 or secp256r1"
+            ),
+        }
+    }
+
+    /// `self + scalar·G` for a big-endian scalar
+    fn add_scalar(self, scalar: &[u8; 32]) -> Result<Self> {
+        match self {
+            Self::K1(point) => {
+                let k = parse_scalar_k1(scalar)?;
+                Ok(Self::K1(point + k256::ProjectivePoint::GENERATOR * k))
+            }
+            Self::R1(point) => {
+                let k = parse_scalar_r1(scalar)?;
+                Ok(Self::R1(point + p256::ProjectivePoint::GENERATOR * k))
+            }
+        }
+    }
+
+    /// Advance to the next candidate: `self + G`
+    #[inline]
+    fn step(&mut self) {
+        match self {
+            Self::K1(point) => *point += k256::ProjectivePoint::GENERATOR,
+            Self::R1(point) => *point += p256::ProjectivePoint::GENERATOR,
+        }
+    }
+
+    #[inline]
+    fn compressed(&self) -> [u8; COMPRESSED_LEN] {
+        let mut out = [0u8; COMPRESSED_LEN];
+        match self {
+            Self::K1(point) => {
+                out.copy_from_slice(point.to_affine().to_encoded_point(true).as_bytes())
+            }
+            Self::R1(point) => {
+                out.copy_from_slice(point.to_affine().to_encoded_point(true).as_bytes())
+            }
+        }
+        out
+    }
+}
+
+fn parse_scalar_k1(bytes: &[u8; 32]) -> Result<k256::Scalar> {
+    Option::from(k256::Scalar::from_repr((*bytes).into()))
+        .context("Scalar is not below the secp256k1 group order")
+}
+
+fn parse_scalar_r1(bytes: &[u8; 32]) -> Result<p256::Scalar> {
+    Option::from(p256::Scalar::from_repr((*bytes).into()))
+        .context("Scalar is not below the secp256r1 group order")
+}
+
+/// `(a + b) mod n` for big-endian scalars on the scheme's curve
+fn add_scalars(scheme: KeyScheme, a: &[u8; 32], b: &[u8; 32]) -> Result<[u8; 32]> {
+    let sum: [u8; 32] = match scheme {
+        KeyScheme::Secp256k1 => {
+            let sum = parse_scalar_k1(a)? + parse_scalar_k1(b)?;
+            if bool::from(sum.is_zero()) {
+                anyhow::bail!("Combined key is zero");
+            }
+            sum.to_repr().into()
+        }
+        KeyScheme::Secp256r1 => {
+            let sum = parse_scalar_r1(a)? + parse_scalar_r1(b)?;
+            if bool::from(sum.is_zero()) {
+                anyhow::bail!("Combined key is zero");
+            }
+            sum.to_repr().into()
+        }
+        KeyScheme::Ed25519 => anyhow::bail!("Split keys are not supported for ed25519"),
+    };
+    Ok(sum)
+}
+
+fn random_scalar(scheme: KeyScheme, rng: &mut StdRng) -> [u8; 32] {
+    match scheme {
+        KeyScheme::Secp256k1 => k256::Scalar::random(rng).to_repr().into(),
+        KeyScheme::Secp256r1 => p256::Scalar::random(rng).to_repr().into(),
+        KeyScheme::Ed25519 => unreachable!("split-key modes are only built for secp schemes"),
+    }
+}
+
+/// `base + n` as a big-endian 256-bit integer (wrapping)
+fn add_counter(base: &[u8; 32], n: u64) -> [u8; 32] {
+    let mut out = *base;
+    let mut carry = n as u128;
+    for byte in out.iter_mut().rev() {
+        if carry == 0 {
+            break;
+        }
+        let sum = *byte as u128 + (carry & 0xff);
+        *byte = sum as u8;
+        carry = (carry >> 8) + (sum >> 8);
+    }
+    out
+}
+
+/// Parse a public key given as hex or Sui base64, with or without the scheme flag
+pub fn parse_public_key(scheme: KeyScheme, input: &str) -> Result<[u8; COMPRESSED_LEN]> {
+    let input = input.trim();
+    let stripped = input.strip_prefix("0x").unwrap_or(input);
+    let bytes = hex::decode(stripped)
+        .or_else(|_| general_purpose::STANDARD.decode(input))
+        .context("Public key must be hex or base64")?;
+
+    let key = match bytes.len() {
+        COMPRESSED_LEN => &bytes[..],
+        len if len == COMPRESSED_LEN + 1 => {
+            if bytes[0] != scheme.flag() {
+                anyhow::bail!(
+                    "Public key flag 0x{:02x} does not match scheme {}",
+                    bytes[0],
+                    scheme
+                );
+            }
+            &bytes[1..]
+        }
+        len => anyhow::bail!(
+            "Public key must be a 33-byte compressed key (optionally prefixed with the flag), got {} bytes",
+            len
+        ),
+    };
+
+    // Validate the point before mining against it
+    CurvePoint::decompress(scheme, key)?;
+    let mut out = [0u8; COMPRESSED_LEN];
+    out.copy_from_slice(key);
+    Ok(out)
+}
+
+/// Parse a private key given as `suiprivkey…` Bech32, Sui base64 or 32-byte hex
+pub fn parse_private_key(scheme: KeyScheme, input: &str) -> Result<[u8; 32]> {
+    let input = input.trim();
+    let (flag, secret) = if let Ok(keypair) = SuiKeyPair::decode(input) {
+        let bytes = keypair.to_bytes();
+        (Some(bytes[0]), bytes[1..].to_vec())
+    } else if let Ok(bytes) = hex::decode(input.strip_prefix("0x").unwrap_or(input)) {
+        (None, bytes)
+    } else {
+        let bytes = general_purpose::STANDARD
+            .decode(input)
+            .context("Private key must be suiprivkey, base64 or hex")?;
+        match bytes.len() {
+            33 => (Some(bytes[0]), bytes[1..].to_vec()),
+            _ => (None, bytes),
+        }
+    };
+
+    if let Some(flag) = flag
+        && flag != scheme.flag()
+    {
+        anyhow::bail!(
+            "Private key flag 0x{:02x} does not match scheme {}",
+            flag,
+            scheme
+        );
+    }
+    secret
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Private key must be 32 bytes"))
+}
+
+/// Parse a mined offset (32-byte hex)
+pub fn parse_offset(input: &str) -> Result<[u8; 32]> {
+    let input = input.trim();
+    hex::decode(input.strip_prefix("0x").unwrap_or(input))
+        .context("Offset must be hex")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Offset must be 32 bytes"))
+}
+
+/// Address of the combined public key `P + offset·G`
+pub fn combined_address(
+    scheme: KeyScheme,
+    public_key: &[u8; COMPRESSED_LEN],
+    offset: &[u8; 32],
+) -> Result<[u8; 32]> {
+    let point = CurvePoint::decompress(scheme, public_key)?.add_scalar(offset)?;
+    Ok(derive_address(scheme.flag(), &point.compressed()))
+}
+
+/// Combine the user's secret with a mined offset into the final keypair
+pub fn combine(scheme: KeyScheme, secret: &[u8; 32], offset: &[u8; 32]) -> Result<FoundKey> {
+    let combined = add_scalars(scheme, secret, offset)?;
+    let keypair = FoundKey::new(scheme, [0u8; 32], combined.to_vec()).keypair()?;
+    let address = SuiAddress::from(&keypair.public());
+    let key = FoundKey::new(scheme, address.to_inner(), combined.to_vec());
+    key.verify()?;
+    Ok(key)
+}
+
+/// A mined offset; it is useless without the user's secret key
+#[derive(Clone, Debug)]
+pub struct FoundOffset {
+    pub address: [u8; 32],
+    pub offset: [u8; 32],
+}
+
+/// Per-thread state: the current candidate point and its offset from the user's key
+pub struct SplitWorker {
+    base_offset: [u8; 32],
+    steps: u64,
+    point: CurvePoint,
+}
+
+/// Searches offsets for a user-supplied public key
+#[derive(Clone, Debug)]
+pub struct SplitKeyMode {
+    pub scheme: KeyScheme,
+    public_key: [u8; COMPRESSED_LEN],
+}
+
+impl SplitKeyMode {
+    pub fn new(scheme: KeyScheme, public_key: [u8; COMPRESSED_LEN]) -> Result<Self> {
+        CurvePoint::decompress(scheme, &public_key)?;
+        Ok(Self { scheme, public_key })
+    }
+}
+
+impl SearchSpace for SplitKeyMode {
+    type Worker = SplitWorker;
+    type Found = FoundOffset;
+
+    fn worker(&self, _thread_index: usize) -> SplitWorker {
+        // Each thread walks P + (r + i)·G from its own random r: one point addition per candidate
+        let mut rng = StdRng::from_rng(OsRng).expect("Failed to seed RNG from the OS");
+        let base_offset = random_scalar(self.scheme, &mut rng);
+        let point = CurvePoint::decompress(self.scheme, &self.public_key)
+            .and_then(|point| point.add_scalar(&base_offset))
+            .expect("public key validated in SplitKeyMode::new");
+        SplitWorker {
+            base_offset,
+            steps: 0,
+            point,
+        }
+    }
+
+    #[inline]
+    fn try_candidate(
+        &self,
+        worker: &mut SplitWorker,
+        target: &TargetChecker,
+    ) -> Option<FoundOffset> {
+        let address = derive_address(self.scheme.flag(), &worker.point.compressed());
+        let steps = worker.steps;
+        worker.point.step();
+        worker.steps += 1;
+
+        if !target.matches(&address) {
+            return None;
+        }
+        let offset = add_counter(&worker.base_offset, steps);
+        // The sum may wrap past the group order in theory; only report offsets that verify
+        let verified = combined_address(self.scheme, &self.public_key, &offset).ok()?;
+        (verified == address).then_some(FoundOffset { address, offset })
+    }
+
+    fn description(&self) -> &'static str {
+        "Split-Key Address"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The user's side: a secret key and the compressed public key they hand out
+    fn user_key(scheme: KeyScheme, seed: u64) -> ([u8; 32], [u8; COMPRESSED_LEN]) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let secret = random_scalar(scheme, &mut rng);
+        let point = match scheme {
+            KeyScheme::Secp256k1 => CurvePoint::K1(k256::ProjectivePoint::IDENTITY),
+            _ => CurvePoint::R1(p256::ProjectivePoint::IDENTITY),
+        };
+        (secret, point.add_scalar(&secret).unwrap().compressed())
+    }
+
+    #[test]
+    fn test_add_counter_carries() {
+        let mut base = [0u8; 32];
+        base[31] = 0xff;
+        base[30] = 0xff;
+        let sum = add_counter(&base, 1);
+        assert_eq!(sum[29..], [1, 0, 0]);
+    }
+
+    #[test]
+    fn test_mined_offset_combines_to_vanity_key() {
+        let target = TargetChecker::from_hex_prefix("d").unwrap();
+        for scheme in [KeyScheme::Secp256k1, KeyScheme::Secp256r1] {
+            let (secret, public) = user_key(scheme, 21);
+            let mode = SplitKeyMode::new(scheme, public).unwrap();
+            let mut worker = mode.worker(0);
+
+            let found = (0..10_000)
+                .find_map(|_| mode.try_candidate(&mut worker, &target))
+                .unwrap();
+            assert!(hex::encode(found.address).starts_with('d'));
+
+            let key = combine(scheme, &secret, &found.offset).unwrap();
+            assert_eq!(key.address, found.address);
+            key.verify().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_ed25519_is_rejected() {
+        assert!(SplitKeyMode::new(KeyScheme::Ed25519, [2u8; COMPRESSED_LEN]).is_err());
+    }
+
+    #[test]
+    fn test_parse_public_key_with_flag() {
+        let (_, public) = user_key(KeyScheme::Secp256r1, 4);
+        let mut flagged = vec![KeyScheme::Secp256r1.flag()];
+        flagged.extend_from_slice(&public);
+        let b64 = general_purpose::STANDARD.encode(&flagged);
+
+        assert_eq!(
+            parse_public_key(KeyScheme::Secp256r1, &b64).unwrap(),
+            public
+        );
+        assert_eq!(
+            parse_public_key(KeyScheme::Secp256r1, &hex::encode(public)).unwrap(),
+            public
+        );
+        assert!(parse_public_key(KeyScheme::Secp256k1, &b64).is_err());
+    }
+}
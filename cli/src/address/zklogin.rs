@@ -0,0 +1,155 @@
+//! zkLogin vanity addresses
+//!
+//! A zkLogin address is `Blake2b256(0x05 || len(iss) || iss || address_seed)`, where
+//! `address_seed = Poseidon(key claim name, key claim value, aud, Poseidon(user salt))` over
+//! BN254. The OAuth claims are fixed, so the search varies the user salt.
+
+use crate::mining::search::SearchSpace;
+use crate::target::TargetChecker;
+
+use anyhow::Result;
+use fastcrypto_zkp::bn254::utils::{gen_address_seed, get_zk_login_address};
+use fastcrypto_zkp::bn254::zk_login::Bn254FrElement;
+use rand::rngs::{OsRng, StdRng};
+use rand::{Rng, SeedableRng};
+use std::str::FromStr;
+
+/// Longest issuer accepted in an address (its length is hashed as one byte)
+const MAX_ISS_LEN: usize = 255;
+
+/// A mined user salt
+#[derive(Clone, Debug)]
+pub struct FoundSalt {
+    /// Salt as a decimal string, the format salt services return
+    pub salt: String,
+    pub address_seed: String,
+    pub address: [u8; 32],
+}
+
+/// Address search over user salts for fixed OAuth claims
+#[derive(Clone, Debug)]
+pub struct ZkLoginMode {
+    pub iss: String,
+    pub aud: String,
+    /// Name of the key claim, usually `sub`
+    pub key_claim_name: String,
+    pub key_claim_value: String,
+}
+
+impl ZkLoginMode {
+    pub fn new(iss: &str, aud: &str, key_claim_name: &str, key_claim_value: &str) -> Result<Self> {
+        if iss.is_empty() || iss.len() > MAX_ISS_LEN {
+            anyhow::bail!("Issuer must be 1 to {} bytes", MAX_ISS_LEN);
+        }
+        let mode = Self {
+            iss: iss.to_string(),
+            aud: aud.to_string(),
+            key_claim_name: key_claim_name.to_string(),
+            key_claim_value: key_claim_value.to_string(),
+        };
+        // Surfaces claims that are too long for the circuit before mining starts
+        mode.derive(0)?;
+        Ok(mode)
+    }
+
+    /// Derive the address seed and address for a salt
+    pub fn derive(&self, salt: u128) -> Result<FoundSalt> {
+        let salt = salt.to_string();
+        let address_seed = gen_address_seed(
+            &salt,
+            &self.key_claim_name,
+            &self.key_claim_value,
+            &self.aud,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to derive address seed: {}", e))?;
+        let seed = Bn254FrElement::from_str(&address_seed)
+            .map_err(|e| anyhow::anyhow!("Invalid address seed: {}", e))?;
+        let address = get_zk_login_address(&seed, &self.iss)
+            .map_err(|e| anyhow::anyhow!("Failed to derive zkLogin address: {}", e))?;
+        Ok(FoundSalt {
+            salt,
+            address_seed,
+            address,
+        })
+    }
+}
+
+impl SearchSpace for ZkLoginMode {
+    /// Next salt to try; threads start at independent random salts
+    type Worker = u128;
+    type Found = FoundSalt;
+
+    fn worker(&self, _thread_index: usize) -> u128 {
+        let mut rng = StdRng::from_rng(OsRng).expect("Failed to seed RNG from the OS");
+        rng.r#gen()
+    }
+
+    fn try_candidate(&self, salt: &mut u128, target: &TargetChecker) -> Option<FoundSalt> {
+        let current = *salt;
+        // Advance first, so a salt that fails to derive isn't retried forever
+        *salt = salt.wrapping_add(1);
+        let found = self.derive(current).ok()?;
+        target.matches(&found.address).then_some(found)
+    }
+
+    fn description(&self) -> &'static str {
+        "zkLogin Address"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode() -> ZkLoginMode {
+        ZkLoginMode::new(
+            "https://accounts.google.com",
+            "575519204237-msop9ep45u2uo98hapqmngv8d84qdc8k.apps.googleusercontent.com",
+            "sub",
+            "110463452167303598383",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_known_address() {
+        // `jwtToAddress` vector from the Sui TypeScript SDK
+        let mode = ZkLoginMode::new(
+            "https://oauth.sui.io",
+            "test",
+            "sub",
+            "8c2d7d66-87af-41fa-b6fc-63e8bb71fab4",
+        )
+        .unwrap();
+        let found = mode
+            .derive(248191903847969014646285995941615069143)
+            .unwrap();
+        assert_eq!(
+            hex::encode(found.address),
+            "22cebcf68a9d75d508d50d553dd6bae378ef51177a3f3a5b8bea8c3bb2c6a3c4"
+        );
+    }
+
+    #[test]
+    fn test_salt_changes_address() {
+        let mode = mode();
+        let a = mode.derive(1).unwrap();
+        assert_eq!(a.address, mode.derive(1).unwrap().address);
+        assert_ne!(a.address, mode.derive(2).unwrap().address);
+    }
+
+    #[test]
+    fn test_found_salt_rederives() {
+        let mode = mode();
+        let target = TargetChecker::from_hex_prefix("a").unwrap();
+        let mut salt = 1_000u128;
+
+        let found = (0..2_000)
+            .find_map(|_| mode.try_candidate(&mut salt, &target))
+            .unwrap();
+        assert!(hex::encode(found.address).starts_with('a'));
+
+        let salt: u128 = found.salt.parse().unwrap();
+        assert_eq!(mode.derive(salt).unwrap().address, found.address);
+    }
+}
@@ -1,14 +1,513 @@
+use crate::mining::mode::MiningMode;
+use crate::mining::{BestSearch, IndexTarget, NONCE_PLACEHOLDER, NonceStrategy, SearchBudget};
+use crate::move_build::PublishedDependency;
+use crate::package_deps::referenced_packages;
+use crate::score::Scorer;
+use crate::target::TargetChecker;
 use anyhow::{Context, Result};
 use rand::Rng;
 use rand::rngs::OsRng;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
 use sui_types::{
+    Identifier, SUI_FRAMEWORK_PACKAGE_ID,
     base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress},
+    move_package::MovePackage,
     programmable_transaction_builder::ProgrammableTransactionBuilder,
     transaction::{
-        GasData, TransactionData, TransactionDataV1, TransactionExpiration, TransactionKind,
+        CallArg, GasData, ObjectArg, TransactionData, TransactionDataV1, TransactionExpiration,
+        TransactionKind,
     },
 };
 
+/// Target pattern options shared by the CLI subcommands and the WebSocket messages
+#[derive(clap::Args, Deserialize, Debug, Clone, Default)]
+pub struct TargetSpec {
+    /// Hex prefix to search for (without 0x)
+    #[arg(short, long, default_value = "")]
+    #[serde(default)]
+    pub prefix: String,
+
+    /// Hex suffix to search for (without 0x), matched against the end of the ID
+    #[arg(long, default_value = "")]
+    #[serde(default)]
+    pub suffix: String,
+
+    /// Masked pattern: hex chars are fixed, '?' is any nibble, one '*' fills the gap
+    /// (e.g. cafe??00 or cafe*0000)
+    #[arg(long, default_value = "", conflicts_with_all = ["prefix", "suffix"])]
+    #[serde(default)]
+    pub pattern: String,
+
+    /// Match any of several hex prefixes (comma separated, e.g. cafe,dead,beef)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        num_args = 1..,
+        conflicts_with_all = ["prefix", "suffix", "pattern"]
+    )]
+    #[serde(default)]
+    pub prefixes: Vec<String>,
+
+    /// File with one hex prefix per line to match any of (blank lines and # comments ignored)
+    #[arg(long, conflicts_with_all = ["prefix", "suffix", "pattern"])]
+    #[serde(skip)]
+    pub prefixes_file: Option<PathBuf>,
+
+    /// Regex over the lowercase hex ID including "0x" (e.g. ^0x(dead|beef))
+    #[arg(
+        long,
+        default_value = "",
+        conflicts_with_all = ["prefix", "suffix", "pattern", "prefixes", "prefixes_file"]
+    )]
+    #[serde(default)]
+    pub regex: String,
+}
+
+impl TargetSpec {
+    /// Build the TargetChecker described by these options
+    pub fn build(&self) -> Result<TargetChecker> {
+        if !self.regex.is_empty() {
+            if !self.prefix.is_empty()
+                || !self.suffix.is_empty()
+                || !self.pattern.is_empty()
+                || !self.prefixes.is_empty()
+                || self.prefixes_file.is_some()
+            {
+                anyhow::bail!("regex cannot be combined with other target options");
+            }
+            return TargetChecker::from_regex(&self.regex);
+        }
+
+        let mut prefixes = self.prefixes.clone();
+        if let Some(path) = &self.prefixes_file {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read prefixes file {}", path.display()))?;
+            prefixes.extend(
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(String::from),
+            );
+        }
+
+        if !prefixes.is_empty() {
+            if !self.prefix.is_empty() || !self.suffix.is_empty() || !self.pattern.is_empty() {
+                anyhow::bail!("prefixes cannot be combined with prefix, suffix or pattern");
+            }
+            return TargetChecker::from_prefix_set(prefixes);
+        }
+
+        if !self.pattern.is_empty() {
+            if !self.prefix.is_empty() || !self.suffix.is_empty() {
+                anyhow::bail!("pattern cannot be combined with prefix or suffix");
+            }
+            return TargetChecker::from_pattern(&self.pattern);
+        }
+        TargetChecker::from_hex_parts(&self.prefix, &self.suffix)
+    }
+
+    /// True if any target option was given
+    fn is_set(&self) -> bool {
+        !self.prefix.is_empty()
+            || !self.suffix.is_empty()
+            || !self.pattern.is_empty()
+            || !self.prefixes.is_empty()
+            || self.prefixes_file.is_some()
+            || !self.regex.is_empty()
+    }
+}
+
+/// Score-based "best-so-far" options shared by the CLI subcommands and the WebSocket messages
+#[derive(clap::Args, Deserialize, Debug, Clone, Default)]
+pub struct ScoreSpec {
+    /// Keep the best-scoring IDs instead of matching a target
+    /// (leading-zeros, repeat-run or dictionary)
+    #[arg(
+        long,
+        conflicts_with_all = ["prefix", "suffix", "pattern", "prefixes", "prefixes_file", "regex"]
+    )]
+    #[serde(default)]
+    pub score: Option<String>,
+
+    /// Hex words for the dictionary score (comma separated, e.g. cafe,dead,beef)
+    #[arg(long, value_delimiter = ',', num_args = 1.., requires = "score")]
+    #[serde(default)]
+    pub words: Vec<String>,
+
+    /// Number of best IDs to keep
+    #[arg(long, default_value = "5")]
+    #[serde(default)]
+    pub top: usize,
+
+    /// Stop the score search after this many seconds (default: until cancelled)
+    #[arg(long, requires = "score")]
+    #[serde(default)]
+    pub max_time: Option<u64>,
+
+    /// Stop the score search after this many attempts (default: until cancelled)
+    #[arg(long, requires = "score")]
+    #[serde(default)]
+    pub max_attempts: Option<u64>,
+}
+
+impl ScoreSpec {
+    /// Build the best-so-far search, or None if no score was requested
+    pub fn build(&self) -> Result<Option<BestSearch>> {
+        let Some(name) = &self.score else {
+            return Ok(None);
+        };
+        let scorer = Scorer::from_name(name, &self.words)?;
+        let top = if self.top == 0 { 5 } else { self.top };
+        let budget = SearchBudget {
+            max_time: self.max_time.map(Duration::from_secs),
+            max_attempts: self.max_attempts,
+        };
+        Ok(Some(BestSearch::new(scorer, top).with_budget(budget)))
+    }
+}
+
+/// Continuous mining options: keep going after the first match
+#[derive(clap::Args, Deserialize, Debug, Clone, Default)]
+pub struct StreamSpec {
+    /// Keep mining after a match and print every match as a JSON line
+    #[arg(long)]
+    #[serde(default)]
+    pub all: bool,
+
+    /// Stop after this many matches (implies --all)
+    #[arg(long)]
+    #[serde(default)]
+    pub count: Option<u64>,
+}
+
+impl StreamSpec {
+    pub fn is_set(&self) -> bool {
+        self.all || self.count.is_some()
+    }
+}
+
+/// Created-object target options for package mining
+#[derive(clap::Args, Deserialize, Debug, Clone, Default)]
+pub struct ObjectsSpec {
+    /// Created-object index the main target applies to (0 = package, 1.. = objects created
+    /// by `init` in creation order; see --list-objects)
+    #[arg(long, default_value = "0")]
+    #[serde(default)]
+    pub object_index: u16,
+
+    /// Extra created object that must also match, as INDEX:PATTERN (e.g. 1:c0ffee*); repeatable
+    #[arg(
+        long = "object",
+        value_name = "INDEX:PATTERN",
+        conflicts_with = "score"
+    )]
+    #[serde(default)]
+    pub objects: Vec<String>,
+
+    /// Hex prefix the UpgradeCap ID must also start with
+    #[arg(long, default_value = "", conflicts_with = "score")]
+    #[serde(default)]
+    pub cap_prefix: String,
+
+    /// Masked pattern the UpgradeCap ID must also match (e.g. 0000*)
+    #[arg(long, default_value = "", conflicts_with_all = ["cap_prefix", "score"])]
+    #[serde(default)]
+    pub cap_pattern: String,
+
+    /// Created-object index of the UpgradeCap (see --list-objects); 1 when no module defines
+    /// `init`, otherwise found by dry-running the publish
+    #[arg(long)]
+    #[serde(default)]
+    pub cap_index: Option<u16>,
+}
+
+impl ObjectsSpec {
+    /// Whether an UpgradeCap target was given without its index
+    ///
+    /// The cap comes right after the package unless `init` creates objects first, so the
+    /// default index only holds for packages without `init`.
+    pub fn guesses_cap_index(&self) -> bool {
+        self.cap_index.is_none() && !(self.cap_prefix.is_empty() && self.cap_pattern.is_empty())
+    }
+
+    /// Build the extra targets (init objects and UpgradeCap), empty if none were given
+    pub fn build(&self) -> Result<Vec<IndexTarget>> {
+        let mut extra = Vec::new();
+        for spec in &self.objects {
+            let (index, pattern) = spec
+                .split_once(':')
+                .with_context(|| format!("Object target must be INDEX:PATTERN, got {}", spec))?;
+            let index: u16 = index
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid object index in {}", spec))?;
+            let target = TargetChecker::from_pattern(pattern.trim())
+                .with_context(|| format!("Invalid object pattern in {}", spec))?;
+            extra.push(IndexTarget { index, target });
+        }
+
+        let cap_index = self.cap_index.unwrap_or(1);
+        if cap_index == 0 {
+            anyhow::bail!("cap index must be at least 1, index 0 is the package itself");
+        }
+        let cap_target = match (self.cap_prefix.is_empty(), self.cap_pattern.is_empty()) {
+            (true, true) => None,
+            (false, true) => Some(TargetChecker::from_hex_prefix(&self.cap_prefix)?),
+            (true, false) => Some(TargetChecker::from_pattern(&self.cap_pattern)?),
+            (false, false) => anyhow::bail!("cap prefix cannot be combined with cap pattern"),
+        };
+        if let Some(target) = cap_target {
+            extra.push(IndexTarget {
+                index: cap_index,
+                target,
+            });
+        }
+
+        for (i, object) in extra.iter().enumerate() {
+            if object.index == self.object_index
+                || extra[..i].iter().any(|o| o.index == object.index)
+            {
+                anyhow::bail!("object index {} is targeted more than once", object.index);
+            }
+        }
+        Ok(extra)
+    }
+}
+
+/// Publish dependency options for package mining
+#[derive(clap::Args, Deserialize, Debug, Clone, Default)]
+pub struct DependencySpec {
+    /// Extra dependency package IDs (comma-separated), e.g. transitive dependencies or the
+    /// current version of an upgraded library; the bytecode's own references are always added
+    #[arg(long, value_delimiter = ',', num_args = 1..)]
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+
+    /// Use exactly --dependencies instead of the bytecode's references and Move.lock
+    #[arg(long, requires = "dependencies")]
+    #[serde(default)]
+    pub exact_dependencies: bool,
+}
+
+impl DependencySpec {
+    /// Dependency package IDs for publishing `modules`, sorted
+    ///
+    /// A locked dependency's published address replaces its original ID, which is what the
+    /// bytecode refers to after an upgrade.
+    pub fn build(
+        &self,
+        modules: &[Vec<u8>],
+        published: &[PublishedDependency],
+    ) -> Result<Vec<ObjectID>> {
+        use std::str::FromStr;
+
+        let mut dependencies = BTreeSet::new();
+        if !self.exact_dependencies {
+            dependencies = referenced_packages(modules)?;
+            for dep in published {
+                let Some(published_at) = dep.published_at else {
+                    continue;
+                };
+                if let Some(original_id) = dep.original_id {
+                    dependencies.remove(&original_id);
+                }
+                dependencies.insert(published_at);
+            }
+        }
+        for id in &self.dependencies {
+            dependencies.insert(
+                ObjectID::from_str(id).with_context(|| format!("Invalid dependency ID {}", id))?,
+            );
+        }
+        if dependencies.is_empty() {
+            anyhow::bail!("A package needs at least one dependency");
+        }
+        Ok(dependencies.into_iter().collect())
+    }
+}
+
+/// Object index options for Move call mining
+#[derive(clap::Args, Deserialize, Debug, Clone, Default)]
+pub struct IndexSpec {
+    /// Require the match at this exact object index (default: 0)
+    #[arg(long, conflicts_with = "index_range")]
+    #[serde(default)]
+    pub object_index: Option<u16>,
+
+    /// Check every object index in A..B (or A..=B) and report which one matched,
+    /// for PTBs that create several objects
+    #[arg(long, value_name = "A..B")]
+    #[serde(default)]
+    pub index_range: Option<String>,
+}
+
+/// Object indices a Move call mining run checks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectIndices {
+    Single(u16),
+    /// Half-open `start..end`
+    Range(u16, u16),
+}
+
+impl IndexSpec {
+    pub fn build(&self) -> Result<ObjectIndices> {
+        let Some(range) = &self.index_range else {
+            return Ok(ObjectIndices::Single(self.object_index.unwrap_or(0)));
+        };
+        if self.object_index.is_some() {
+            anyhow::bail!("object index cannot be combined with an index range");
+        }
+
+        let parse = |s: &str| -> Result<u16> {
+            s.trim()
+                .parse()
+                .with_context(|| format!("Invalid index range {}, expected A..B", range))
+        };
+        let (start, end) = match range.split_once("..=") {
+            Some((start, end)) => (
+                parse(start)?,
+                parse(end)?
+                    .checked_add(1)
+                    .context("Index range end too large")?,
+            ),
+            None => {
+                let (start, end) = range
+                    .split_once("..")
+                    .with_context(|| format!("Invalid index range {}, expected A..B", range))?;
+                (parse(start)?, parse(end)?)
+            }
+        };
+        if start >= end {
+            anyhow::bail!("Index range {} is empty", range);
+        }
+        Ok(ObjectIndices::Range(start, end))
+    }
+}
+
+impl std::fmt::Display for ObjectIndices {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Single(index) => write!(f, "{}", index),
+            Self::Range(start, end) => write!(f, "{}..{} (any)", start, end),
+        }
+    }
+}
+
+/// Transaction field varied while mining
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NonceField {
+    /// Expiration epoch (far-future epochs are rejected by some wallets and sponsors)
+    #[default]
+    Epoch,
+    /// Gas budget, from --gas-budget up to --max-gas-budget
+    GasBudget,
+    /// Gas price, from the reference price (or --gas-price) up to --max-gas-price
+    GasPrice,
+    /// An extra, unused pure u64 input appended to the PTB
+    Input,
+}
+
+/// Nonce options shared by the transaction mining subcommands and WebSocket messages
+#[derive(clap::Args, Deserialize, Debug, Clone, Default)]
+pub struct NonceSpec {
+    /// Transaction field to vary while mining
+    #[arg(long = "nonce", value_enum, default_value = "epoch")]
+    #[serde(default, rename = "nonce")]
+    pub field: NonceField,
+
+    /// Highest gas budget tried with --nonce gas-budget
+    #[arg(long)]
+    #[serde(default)]
+    pub max_gas_budget: Option<u64>,
+
+    /// Highest gas price tried with --nonce gas-price
+    #[arg(long)]
+    #[serde(default)]
+    pub max_gas_price: Option<u64>,
+}
+
+impl NonceSpec {
+    pub fn build(&self) -> Result<NonceStrategy> {
+        Ok(match self.field {
+            NonceField::Epoch => NonceStrategy::Epoch,
+            NonceField::GasBudget => NonceStrategy::GasBudget {
+                max: self
+                    .max_gas_budget
+                    .context("gas budget nonces need a max gas budget")?,
+            },
+            NonceField::GasPrice => NonceStrategy::GasPrice {
+                max: self
+                    .max_gas_price
+                    .context("gas price nonces need a max gas price")?,
+            },
+            NonceField::Input => NonceStrategy::PureInput,
+        })
+    }
+}
+
+/// What a mining run is looking for
+pub enum MiningGoal {
+    /// Stop at the first ID matching the target
+    Target(TargetChecker),
+    /// Keep the best-scoring IDs until the budget is spent
+    Best(BestSearch),
+    /// Report every ID matching the target, until `count` matches or cancelled
+    All {
+        target: TargetChecker,
+        count: Option<u64>,
+    },
+}
+
+impl MiningGoal {
+    pub fn build(target: &TargetSpec, score: &ScoreSpec) -> Result<Self> {
+        match score.build()? {
+            Some(search) => {
+                if target.is_set() {
+                    anyhow::bail!("score cannot be combined with target options");
+                }
+                Ok(Self::Best(search))
+            }
+            None => Ok(Self::Target(target.build()?)),
+        }
+    }
+
+    /// Turn a target goal into a streaming one if requested
+    pub fn streaming(self, stream: &StreamSpec) -> Result<Self> {
+        if !stream.is_set() {
+            return Ok(self);
+        }
+        match self {
+            Self::Target(target) => Ok(Self::All {
+                target,
+                count: stream.count,
+            }),
+            Self::Best(_) => anyhow::bail!("--all/--count cannot be combined with score"),
+            all @ Self::All { .. } => Ok(all),
+        }
+    }
+}
+
+/// Score mining ranks each derived ID on its own, so it can't require several matching
+/// objects per transaction or apply to the digest
+pub fn check_scorable<M: MiningMode>(mode: &M) -> Result<()> {
+    if mode.min_matches() > 1 {
+        anyhow::bail!(
+            "score cannot be combined with min matches ({} required)",
+            mode.min_matches()
+        );
+    }
+    if mode.matches_digest() {
+        anyhow::bail!("score applies to object IDs, not the transaction digest");
+    }
+    Ok(())
+}
+
 pub fn format_large_number(n: u64) -> String {
     if n >= 1_000_000_000_000 {
         format!("{:.2}T", n as f64 / 1_000_000_000_000.0)
@@ -23,7 +522,23 @@ pub fn format_large_number(n: u64) -> String {
     }
 }
 
-pub fn randomize_gas_budget(base_gas_budget: u64) -> (u64, u64) {
+/// Nonce a fresh search starts at
+///
+/// Unbounded fields start at a random nonce so separate runs don't repeat each other's work,
+/// above current epochs and far from overflowing. Windowed fields start at the bottom of
+/// their window.
+pub fn random_start_nonce(nonce: NonceStrategy) -> u64 {
+    if nonce.max().is_some() {
+        return 0;
+    }
+    OsRng.gen_range(100_000..(u64::MAX - 1_000_000_000))
+}
+
+/// Gas budget nonces search a window starting at the exact budget, so it's left as is
+pub fn randomize_gas_budget(base_gas_budget: u64, nonce: NonceStrategy) -> (u64, u64) {
+    if matches!(nonce, NonceStrategy::GasBudget { .. }) {
+        return (base_gas_budget, 0);
+    }
     let mut rng = OsRng;
     let extra_gas: u64 = rng.gen_range(0..100_000);
     let effective = base_gas_budget + extra_gas;
@@ -33,20 +548,18 @@ pub fn randomize_gas_budget(base_gas_budget: u64) -> (u64, u64) {
 pub fn create_tx_template(
     sender: SuiAddress,
     module_bytes: Vec<Vec<u8>>,
+    dependencies: Vec<ObjectID>,
     base_gas_budget: u64,
     gas_price: u64,
     gas_payment: (ObjectID, SequenceNumber, ObjectDigest),
+    nonce: NonceStrategy,
 ) -> Result<(Vec<u8>, usize)> {
-    use std::str::FromStr;
-
-    let dependencies = vec![ObjectID::from_str("0x1")?, ObjectID::from_str("0x2")?];
     let mut ptb = ProgrammableTransactionBuilder::new();
     let upgrade_cap = ptb.publish_upgradeable(module_bytes, dependencies);
     ptb.transfer_arg(sender, upgrade_cap);
     let pt = ptb.finish();
 
-    // Manually construct TransactionData to set Expiration
-    // We use the expiration epoch as the "nonce" to crunch, preserving the gas budget
+    // Manually construct TransactionData so the nonce field can be located
 
     // Gas Data with ACTUAL budget (passed in, potentially randomized)
     let gas_data = GasData {
@@ -56,30 +569,70 @@ pub fn create_tx_template(
         budget: base_gas_budget,
     };
 
-    // Placeholder Epoch for finding offset
-    let placeholder_epoch = 0xAAAAAAAAAAAAAAAAu64;
-    let expiration = TransactionExpiration::Epoch(placeholder_epoch);
-
-    let kind = TransactionKind::ProgrammableTransaction(pt);
-
-    let tx_data = TransactionData::V1(TransactionDataV1 {
-        kind,
+    let tx_data = TransactionDataV1 {
+        kind: TransactionKind::ProgrammableTransaction(pt),
         sender,
         gas_data,
-        expiration,
-    });
+        expiration: TransactionExpiration::None,
+    };
 
-    // Serialize
-    let tx_bytes = bcs::to_bytes(&tx_data)?;
+    finish_template(tx_data, nonce)
+}
 
-    // Find the epoch offset (look for our placeholder pattern)
-    // TransactionExpiration::Epoch(u64) serializes as [variant_idx(1), u64(8)]
-    // We look for the u64 bytes.
-    let placeholder_bytes = placeholder_epoch.to_le_bytes();
-    let nonce_offset = find_pattern(&tx_bytes, &placeholder_bytes)
-        .context("Could not find expiration epoch placeholder in transaction bytes")?;
+/// Create a package upgrade transaction template for mining the new package version ID
+/// The PTB authorizes the upgrade with the UpgradeCap, runs `Upgrade` with the new modules and
+/// commits the receipt; the new package is the first object created (index 0)
+pub fn create_upgrade_tx_template(
+    sender: SuiAddress,
+    package_id: ObjectID,
+    upgrade_cap: (ObjectID, SequenceNumber, ObjectDigest),
+    upgrade_policy: u8,
+    module_bytes: Vec<Vec<u8>>,
+    dependencies: Vec<ObjectID>,
+    base_gas_budget: u64,
+    gas_price: u64,
+    gas_payment: (ObjectID, SequenceNumber, ObjectDigest),
+    nonce: NonceStrategy,
+) -> Result<(Vec<u8>, usize)> {
+    let digest =
+        MovePackage::compute_digest_for_modules_and_deps(&module_bytes, &dependencies, true);
 
-    Ok((tx_bytes, nonce_offset))
+    let mut ptb = ProgrammableTransactionBuilder::new();
+    let cap = ptb.obj(ObjectArg::ImmOrOwnedObject(upgrade_cap))?;
+    let policy = ptb.pure(upgrade_policy)?;
+    let digest = ptb.pure(digest.to_vec())?;
+    let ticket = ptb.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        Identifier::new("package")?,
+        Identifier::new("authorize_upgrade")?,
+        vec![],
+        vec![cap, policy, digest],
+    );
+    let receipt = ptb.upgrade(package_id, ticket, dependencies, module_bytes);
+    ptb.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        Identifier::new("package")?,
+        Identifier::new("commit_upgrade")?,
+        vec![],
+        vec![cap, receipt],
+    );
+    let pt = ptb.finish();
+
+    let gas_data = GasData {
+        payment: vec![gas_payment],
+        owner: sender,
+        price: gas_price,
+        budget: base_gas_budget,
+    };
+
+    let tx_data = TransactionDataV1 {
+        kind: TransactionKind::ProgrammableTransaction(pt),
+        sender,
+        gas_data,
+        expiration: TransactionExpiration::None,
+    };
+
+    finish_template(tx_data, nonce)
 }
 
 /// Create a SplitCoins transaction template for mining Gas Coin IDs
@@ -91,6 +644,7 @@ pub fn create_split_tx_template(
     gas_budget: u64,
     gas_price: u64,
     gas_payment: (ObjectID, SequenceNumber, ObjectDigest),
+    nonce: NonceStrategy,
 ) -> Result<(Vec<u8>, usize, u16)> {
     let mut ptb = ProgrammableTransactionBuilder::new();
 
@@ -124,26 +678,14 @@ pub fn create_split_tx_template(
         budget: gas_budget,
     };
 
-    // Placeholder Epoch for finding offset (same as package mining)
-    let placeholder_epoch = 0xAAAAAAAAAAAAAAAAu64;
-    let expiration = TransactionExpiration::Epoch(placeholder_epoch);
-
-    let kind = TransactionKind::ProgrammableTransaction(pt);
-
-    let tx_data = TransactionData::V1(TransactionDataV1 {
-        kind,
+    let tx_data = TransactionDataV1 {
+        kind: TransactionKind::ProgrammableTransaction(pt),
         sender,
         gas_data,
-        expiration,
-    });
-
-    // Serialize
-    let tx_bytes = bcs::to_bytes(&tx_data)?;
+        expiration: TransactionExpiration::None,
+    };
 
-    // Find the epoch offset
-    let placeholder_bytes = placeholder_epoch.to_le_bytes();
-    let nonce_offset = find_pattern(&tx_bytes, &placeholder_bytes)
-        .context("Could not find expiration epoch placeholder in SplitCoins transaction bytes")?;
+    let (tx_bytes, nonce_offset) = finish_template(tx_data, nonce)?;
 
     // Number of new coins created = number of split amounts
     let num_outputs = split_amounts.len() as u16;
@@ -153,38 +695,180 @@ pub fn create_split_tx_template(
 
 /// Create a mining template from existing transaction bytes
 /// This is used for generic Move Calls or other transactions provided by the frontend
-pub fn create_template_from_bytes(original_tx_bytes: &[u8]) -> Result<(Vec<u8>, usize)> {
+/// The transaction keeps its own expiration unless the nonce is the expiration epoch
+pub fn create_template_from_bytes(
+    original_tx_bytes: &[u8],
+    nonce: NonceStrategy,
+) -> Result<(Vec<u8>, usize)> {
     // Deserialize
     let tx_data: TransactionData =
         bcs::from_bytes(original_tx_bytes).context("Failed to deserialize transaction bytes")?;
 
-    // Create placeholder epoch
-    let placeholder_epoch = 0xAAAAAAAAAAAAAAAAu64;
-    let expiration = TransactionExpiration::Epoch(placeholder_epoch);
-
-    // Modify expiration in V1
     // Note: If Sui adds V2 in future, this needs update. Currently only V1 exists.
-    let new_tx_data = match tx_data {
-        TransactionData::V1(mut v1) => {
-            v1.expiration = expiration;
-            TransactionData::V1(v1)
+    match tx_data {
+        TransactionData::V1(v1) => finish_template(v1, nonce),
+    }
+}
+
+/// Put the nonce placeholder into the strategy's field, serialize and locate the field
+///
+/// Windowed fields (gas budget, gas price) get their real value back as the base the
+/// executors add nonces to; epoch and input nonces keep the placeholder as their base.
+fn finish_template(
+    mut tx_data: TransactionDataV1,
+    nonce: NonceStrategy,
+) -> Result<(Vec<u8>, usize)> {
+    let base = match nonce {
+        NonceStrategy::Epoch => NONCE_PLACEHOLDER,
+        NonceStrategy::GasBudget { max } => {
+            let budget = tx_data.gas_data.budget;
+            if max < budget {
+                anyhow::bail!("Max gas budget {} is below the gas budget {}", max, budget);
+            }
+            budget
+        }
+        NonceStrategy::GasPrice { max } => {
+            let price = tx_data.gas_data.price;
+            if max < price {
+                anyhow::bail!("Max gas price {} is below the gas price {}", max, price);
+            }
+            price
+        }
+        NonceStrategy::PureInput => {
+            let TransactionKind::ProgrammableTransaction(pt) = &mut tx_data.kind else {
+                anyhow::bail!("Only programmable transactions can carry a nonce input");
+            };
+            // Appended last, so no command refers to it
+            pt.inputs.push(CallArg::Pure(vec![0; 8]));
+            NONCE_PLACEHOLDER
         }
     };
+    set_nonce_field(&mut tx_data, nonce, NONCE_PLACEHOLDER);
+
+    let mut tx_bytes = bcs::to_bytes(&TransactionData::V1(tx_data.clone()))?;
+    let nonce_offset = nonce_field_offset(&tx_data, nonce)?;
+    let nonce_range = nonce_offset..nonce_offset + 8;
+    if tx_bytes.get(nonce_range.clone()) != Some(&NONCE_PLACEHOLDER.to_le_bytes()[..]) {
+        anyhow::bail!(
+            "The {} is not at its computed offset {}, unexpected transaction layout",
+            nonce.label(),
+            nonce_offset
+        );
+    }
+    tx_bytes[nonce_range.clone()].copy_from_slice(&base.to_le_bytes());
+
+    // Self-check: patching a nonce into the bytes must equal serializing the transaction
+    // with that nonce, i.e. only the 8 nonce bytes change
+    let probe = !base;
+    set_nonce_field(&mut tx_data, nonce, probe);
+    let mut patched = tx_bytes.clone();
+    patched[nonce_range].copy_from_slice(&probe.to_le_bytes());
+    if patched != bcs::to_bytes(&TransactionData::V1(tx_data))? {
+        anyhow::bail!(
+            "Changing the {} at offset {} changes more than its 8 bytes",
+            nonce.label(),
+            nonce_offset
+        );
+    }
 
-    // Serialize
-    let tx_bytes = bcs::to_bytes(&new_tx_data)?;
+    Ok((tx_bytes, nonce_offset))
+}
 
-    // Find offset
-    let placeholder_bytes = placeholder_epoch.to_le_bytes();
-    let nonce_offset = find_pattern(&tx_bytes, &placeholder_bytes).context(
-        "Could not find expiration epoch placeholder in re-serialized transaction bytes",
-    )?;
+/// Write `value` into the strategy's field (the last input for nonce inputs)
+fn set_nonce_field(tx_data: &mut TransactionDataV1, nonce: NonceStrategy, value: u64) {
+    match nonce {
+        NonceStrategy::Epoch => tx_data.expiration = TransactionExpiration::Epoch(value),
+        NonceStrategy::GasBudget { .. } => tx_data.gas_data.budget = value,
+        NonceStrategy::GasPrice { .. } => tx_data.gas_data.price = value,
+        NonceStrategy::PureInput => {
+            if let TransactionKind::ProgrammableTransaction(pt) = &mut tx_data.kind {
+                if let Some(CallArg::Pure(bytes)) = pt.inputs.last_mut() {
+                    *bytes = value.to_le_bytes().to_vec();
+                }
+            }
+        }
+    }
+}
 
-    Ok((tx_bytes, nonce_offset))
+/// Offset of the strategy's field in `bcs(TransactionData::V1(tx_data))`
+///
+/// Computed from the serialized sizes of the fields in front of it, so module bytecode or
+/// pure inputs that happen to contain the placeholder can't be mistaken for the nonce.
+fn nonce_field_offset(tx_data: &TransactionDataV1, nonce: NonceStrategy) -> Result<usize> {
+    // `TransactionData::V1` variant tag
+    const VERSION_TAG_LEN: usize = 1;
+
+    // TransactionDataV1 is (kind, sender, gas_data, expiration) and GasData ends with
+    // (price, budget)
+    let kind_len = bcs::serialized_size(&tx_data.kind)?;
+    let gas_data_end = VERSION_TAG_LEN
+        + kind_len
+        + bcs::serialized_size(&tx_data.sender)?
+        + bcs::serialized_size(&tx_data.gas_data)?;
+
+    Ok(match nonce {
+        // After the `TransactionExpiration::Epoch` variant tag
+        NonceStrategy::Epoch => gas_data_end + 1,
+        NonceStrategy::GasBudget { .. } => gas_data_end - 8,
+        NonceStrategy::GasPrice { .. } => gas_data_end - 16,
+        // A programmable transaction is (inputs, commands), the nonce ends the last input
+        NonceStrategy::PureInput => {
+            let TransactionKind::ProgrammableTransaction(pt) = &tx_data.kind else {
+                anyhow::bail!("Only programmable transactions can carry a nonce input");
+            };
+            VERSION_TAG_LEN + kind_len - bcs::serialized_size(&pt.commands)? - 8
+        }
+    })
 }
 
-fn find_pattern(haystack: &[u8], needle: &[u8]) -> Option<usize> {
-    haystack
-        .windows(needle.len())
-        .position(|window| window == needle)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce_offset_ignores_placeholder_elsewhere() {
+        let gas_payment = (
+            ObjectID::ZERO,
+            SequenceNumber::from_u64(1),
+            ObjectDigest::new([0; 32]),
+        );
+        for nonce in [
+            NonceStrategy::Epoch,
+            NonceStrategy::GasBudget { max: 2_000_000 },
+            NonceStrategy::GasPrice { max: 2_000 },
+            NonceStrategy::PureInput,
+        ] {
+            // The split amount serializes to the placeholder bytes, ahead of every nonce field
+            let (mut tx_bytes, nonce_offset, _) = create_split_tx_template(
+                SuiAddress::ZERO,
+                vec![NONCE_PLACEHOLDER],
+                1_000_000,
+                1_000,
+                gas_payment,
+                nonce,
+            )
+            .unwrap();
+            tx_bytes[nonce_offset..nonce_offset + 8].copy_from_slice(&42u64.to_le_bytes());
+
+            let TransactionData::V1(tx_data) = bcs::from_bytes(&tx_bytes).unwrap();
+            let value = match nonce {
+                NonceStrategy::Epoch => match tx_data.expiration {
+                    TransactionExpiration::Epoch(epoch) => epoch,
+                    other => panic!("unexpected expiration {:?}", other),
+                },
+                NonceStrategy::GasBudget { .. } => tx_data.gas_data.budget,
+                NonceStrategy::GasPrice { .. } => tx_data.gas_data.price,
+                NonceStrategy::PureInput => {
+                    let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind else {
+                        panic!("not a programmable transaction");
+                    };
+                    let Some(CallArg::Pure(bytes)) = pt.inputs.last() else {
+                        panic!("no nonce input");
+                    };
+                    u64::from_le_bytes(bytes.as_slice().try_into().unwrap())
+                }
+            };
+            assert_eq!(value, 42, "{}", nonce);
+        }
+    }
 }
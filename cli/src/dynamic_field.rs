@@ -0,0 +1,271 @@
+//! Dynamic field vanity IDs
+//!
+//! A dynamic field's object ID is
+//! `Blake2b256(0xf0 || parent || len(key) as u64 LE || bcs(key) || bcs(key type tag))`.
+//! No transaction is involved: the parent and key type are fixed and the search walks key
+//! values (a `u64`, or a counter rendered into a string template) until the ID matches.
+
+use crate::mining::search::SearchSpace;
+use crate::target::TargetChecker;
+
+use anyhow::{Context, Result};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use sui_types::TypeTag;
+use sui_types::base_types::ObjectID;
+use sui_types::dynamic_field::derive_dynamic_field_id;
+
+/// Hashing intent scope of child object IDs
+const CHILD_OBJECT_ID_SCOPE: u8 = 0xf0;
+
+/// Key values handed out to a worker at a time
+const CHUNK_SIZE: u64 = 1_000;
+
+/// Placeholder for the varying part of a string key template
+pub const KEY_PLACEHOLDER: &str = "{}";
+
+/// Move type of the dynamic field key
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum KeyType {
+    /// `u64`
+    U64,
+    /// `0x1::string::String`
+    String,
+    /// `0x1::ascii::String`
+    Ascii,
+    /// `vector<u8>`
+    Bytes,
+}
+
+impl KeyType {
+    fn type_name(self) -> &'static str {
+        match self {
+            Self::U64 => "u64",
+            Self::String => "0x1::string::String",
+            Self::Ascii => "0x1::ascii::String",
+            Self::Bytes => "vector<u8>",
+        }
+    }
+}
+
+impl fmt::Display for KeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.type_name())
+    }
+}
+
+/// A mined key and the resulting dynamic field ID
+#[derive(Clone, Debug)]
+pub struct FoundField {
+    /// Counter value the key was built from
+    pub counter: u64,
+    /// The key as it appears in Move (a number or the rendered string)
+    pub key: String,
+    pub key_bytes: Vec<u8>,
+    pub object_id: ObjectID,
+}
+
+/// Field ID search over key values for a fixed parent and key type
+#[derive(Clone, Debug)]
+pub struct DynamicFieldMode {
+    pub parent: ObjectID,
+    pub key_type: KeyType,
+    /// Key template for string-like keys, `{}` is replaced by the counter
+    pub template: String,
+    /// Whether the field is a dynamic object field (key wrapped in `Wrapper<K>`)
+    pub object_field: bool,
+    type_tag: TypeTag,
+    type_tag_bytes: Vec<u8>,
+    /// Next unclaimed counter value, shared so keys stay close to the start value
+    next: Arc<AtomicU64>,
+}
+
+impl DynamicFieldMode {
+    pub fn new(
+        parent: ObjectID,
+        key_type: KeyType,
+        template: &str,
+        start: u64,
+        object_field: bool,
+    ) -> Result<Self> {
+        match key_type {
+            KeyType::U64 if template != KEY_PLACEHOLDER => {
+                anyhow::bail!("u64 keys are the counter itself, a key template isn't supported")
+            }
+            _ if template.matches(KEY_PLACEHOLDER).count() != 1 => {
+                anyhow::bail!("Key template must contain exactly one {}", KEY_PLACEHOLDER)
+            }
+            KeyType::Ascii if !template.is_ascii() => {
+                anyhow::bail!("ASCII string keys can't contain non-ASCII characters")
+            }
+            _ => {}
+        }
+
+        let type_name = if object_field {
+            format!(
+                "0x2::dynamic_object_field::Wrapper<{}>",
+                key_type.type_name()
+            )
+        } else {
+            key_type.type_name().to_string()
+        };
+        let type_tag = TypeTag::from_str(&type_name)
+            .with_context(|| format!("Invalid key type {}", type_name))?;
+        let type_tag_bytes = bcs::to_bytes(&type_tag)?;
+
+        Ok(Self {
+            parent,
+            key_type,
+            template: template.to_string(),
+            object_field,
+            type_tag,
+            type_tag_bytes,
+            next: Arc::new(AtomicU64::new(start)),
+        })
+    }
+
+    /// The key for a counter value, as shown and as BCS bytes
+    pub fn key(&self, counter: u64) -> (String, Vec<u8>) {
+        match self.key_type {
+            KeyType::U64 => (counter.to_string(), counter.to_le_bytes().to_vec()),
+            KeyType::String | KeyType::Ascii | KeyType::Bytes => {
+                let key = self
+                    .template
+                    .replacen(KEY_PLACEHOLDER, &counter.to_string(), 1);
+                let mut bytes = uleb128(key.len());
+                bytes.extend_from_slice(key.as_bytes());
+                (key, bytes)
+            }
+        }
+    }
+
+    #[inline]
+    fn field_id(&self, key_bytes: &[u8]) -> [u8; 32] {
+        let mut hasher = Blake2b256::default();
+        hasher.update([CHILD_OBJECT_ID_SCOPE]);
+        hasher.update(self.parent);
+        hasher.update((key_bytes.len() as u64).to_le_bytes());
+        hasher.update(key_bytes);
+        hasher.update(&self.type_tag_bytes);
+        hasher.finalize().digest
+    }
+
+    /// Re-derive the field ID through `sui-types`
+    pub fn verify(&self, found: &FoundField) -> Result<ObjectID> {
+        let derived = derive_dynamic_field_id(self.parent, &self.type_tag, &found.key_bytes)
+            .map_err(|e| anyhow::anyhow!("Failed to derive dynamic field ID: {}", e))?;
+        if derived != found.object_id {
+            anyhow::bail!(
+                "Derived dynamic field ID mismatch: expected {}, got {}",
+                found.object_id,
+                derived
+            );
+        }
+        Ok(derived)
+    }
+}
+
+/// ULEB128 length prefix used by BCS for vectors and strings
+fn uleb128(mut value: usize) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// A worker's claimed range of counter values
+pub struct CounterRange {
+    next: u64,
+    end: u64,
+}
+
+impl SearchSpace for DynamicFieldMode {
+    type Worker = CounterRange;
+    type Found = FoundField;
+
+    fn worker(&self, _thread_index: usize) -> CounterRange {
+        CounterRange { next: 0, end: 0 }
+    }
+
+    #[inline]
+    fn try_candidate(
+        &self,
+        range: &mut CounterRange,
+        target: &TargetChecker,
+    ) -> Option<FoundField> {
+        if range.next == range.end {
+            range.next = self.next.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+            range.end = range.next.saturating_add(CHUNK_SIZE);
+        }
+        let counter = range.next;
+        range.next += 1;
+
+        let (key, key_bytes) = self.key(counter);
+        let id = self.field_id(&key_bytes);
+        if !target.matches(&id) {
+            return None;
+        }
+        Some(FoundField {
+            counter,
+            key,
+            key_bytes,
+            object_id: ObjectID::new(id),
+        })
+    }
+
+    fn description(&self) -> &'static str {
+        "Dynamic Field ID"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parent() -> ObjectID {
+        ObjectID::from_str("0x5").unwrap()
+    }
+
+    #[test]
+    fn test_field_id_matches_sui_types() {
+        let target = TargetChecker::from_hex_prefix("a").unwrap();
+        for (key_type, template, object_field) in [
+            (KeyType::U64, "{}", false),
+            (KeyType::String, "player-{}", false),
+            (KeyType::Ascii, "{}.sui", true),
+            (KeyType::Bytes, "{}", false),
+        ] {
+            let mode =
+                DynamicFieldMode::new(parent(), key_type, template, 7, object_field).unwrap();
+            let mut range = mode.worker(0);
+            let found = (0..2_000)
+                .find_map(|_| mode.try_candidate(&mut range, &target))
+                .unwrap();
+            assert!(found.counter >= 7);
+            assert_eq!(mode.verify(&found).unwrap(), found.object_id);
+            assert!(hex::encode(found.object_id.as_ref()).starts_with('a'));
+        }
+    }
+
+    #[test]
+    fn test_key_rendering() {
+        let mode = DynamicFieldMode::new(parent(), KeyType::String, "id-{}", 0, false).unwrap();
+        let (key, bytes) = mode.key(42);
+        assert_eq!(key, "id-42");
+        assert_eq!(bytes, bcs::to_bytes(&"id-42".to_string()).unwrap());
+        assert_eq!(uleb128(300), vec![0xac, 0x02]);
+
+        assert!(DynamicFieldMode::new(parent(), KeyType::U64, "x{}", 0, false).is_err());
+        assert!(DynamicFieldMode::new(parent(), KeyType::String, "no-counter", 0, false).is_err());
+        assert!(DynamicFieldMode::new(parent(), KeyType::Ascii, "é{}", 0, false).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! Success probability and confidence estimates for a mining target
+//!
+//! Every attempt is an independent trial, so the number of attempts until a match is
+//! geometrically distributed. A target with per-ID probability `p` checked against `n` IDs
+//! per attempt succeeds with `1 - (1 - p)^n` per attempt, and the chance of having found a
+//! match after `k` attempts is `1 - (1 - p)^(n·k)`. When `k` of the `n` IDs must match, the
+//! per-attempt probability is the binomial tail `P(X >= k)` with `X ~ Binomial(n, p)`.
+
+use crate::target::TargetChecker;
+use std::time::Duration;
+
+/// Confidence levels reported by the estimator
+pub const CONFIDENCE_LEVELS: [f64; 3] = [0.5, 0.9, 0.99];
+
+/// Per-attempt success probability of a search
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    probability: f64,
+    /// ln(1 - p), kept to stay accurate for tiny probabilities
+    log_miss: f64,
+}
+
+impl Estimate {
+    /// Estimate for a per-ID probability checked against `ids_per_attempt` IDs per attempt
+    pub fn new(id_probability: f64, ids_per_attempt: u32) -> Self {
+        let p = id_probability.clamp(0.0, 1.0);
+        let log_miss = (-p).ln_1p() * ids_per_attempt as f64;
+        Self {
+            probability: -log_miss.exp_m1(),
+            log_miss,
+        }
+    }
+
+    /// Estimate when at least `min_matches` of the `ids_per_attempt` IDs must match
+    pub fn at_least(id_probability: f64, ids_per_attempt: u32, min_matches: u32) -> Self {
+        if min_matches <= 1 {
+            return Self::new(id_probability, ids_per_attempt);
+        }
+        let probability = binomial_at_least(id_probability, ids_per_attempt, min_matches);
+        Self {
+            probability,
+            log_miss: (-probability).ln_1p(),
+        }
+    }
+
+    /// Estimate for a target, None if its probability is unknown (e.g. a complex regex)
+    pub fn for_target(target: &TargetChecker, ids_per_attempt: u32) -> Option<Self> {
+        target.probability().map(|p| Self::new(p, ids_per_attempt))
+    }
+
+    /// Estimate for a target where `min_matches` of the IDs per attempt must match
+    pub fn for_target_at_least(
+        target: &TargetChecker,
+        ids_per_attempt: u32,
+        min_matches: u32,
+    ) -> Option<Self> {
+        target
+            .probability()
+            .map(|p| Self::at_least(p, ids_per_attempt, min_matches))
+    }
+
+    /// Probability that a single attempt succeeds
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// Average number of attempts until the first match
+    pub fn mean_attempts(&self) -> f64 {
+        1.0 / self.probability
+    }
+
+    /// Attempts needed to find a match with the given confidence (e.g. 0.9)
+    pub fn attempts_for(&self, confidence: f64) -> u64 {
+        if self.probability >= 1.0 {
+            return 1;
+        }
+        let attempts = (-confidence).ln_1p() / self.log_miss;
+        (attempts.ceil() as u64).max(1)
+    }
+
+    /// Probability that a match has been found after `attempts` attempts
+    pub fn found_by(&self, attempts: u64) -> f64 {
+        if attempts == 0 {
+            return 0.0;
+        }
+        -(self.log_miss * attempts as f64).exp_m1()
+    }
+
+    /// Time needed for `attempts` at the given hashrate (attempts per second)
+    pub fn time_for(attempts: u64, hashrate: f64) -> Option<Duration> {
+        if hashrate > 0.0 {
+            Duration::try_from_secs_f64(attempts as f64 / hashrate).ok()
+        } else {
+            None
+        }
+    }
+}
+
+/// `P(X >= k)` for `X ~ Binomial(n, p)`, summed in log space so tiny `p` doesn't underflow early
+fn binomial_at_least(p: f64, n: u32, k: u32) -> f64 {
+    if k == 0 || p >= 1.0 {
+        return 1.0;
+    }
+    if k > n || p <= 0.0 {
+        return 0.0;
+    }
+
+    let ln_p = p.ln();
+    let ln_miss = (-p).ln_1p();
+    // ln C(n, k)
+    let mut ln_choose: f64 = (0..k).map(|j| ((n - j) as f64 / (j + 1) as f64).ln()).sum();
+    let mut total = 0.0;
+    for i in k..=n {
+        total += (ln_choose + i as f64 * ln_p + (n - i) as f64 * ln_miss).exp();
+        ln_choose += ((n - i) as f64 / (i + 1) as f64).ln();
+    }
+    total.min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_id_prefix() {
+        let target = TargetChecker::from_hex_prefix("cafe").unwrap();
+        let estimate = Estimate::for_target(&target, 1).unwrap();
+        assert!((estimate.probability() - 1.0 / 65536.0).abs() < 1e-15);
+        assert!((estimate.mean_attempts() - 65536.0).abs() < 1e-6);
+        // Median of a geometric distribution is ~ln(2)/p
+        assert_eq!(estimate.attempts_for(0.5), 45426);
+        assert!((estimate.found_by(45426) - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_multiple_ids_per_attempt() {
+        let single = Estimate::new(1.0 / 256.0, 1);
+        let multi = Estimate::new(1.0 / 256.0, 4);
+        let expected = 1.0 - (255.0f64 / 256.0).powi(4);
+        assert!((multi.probability() - expected).abs() < 1e-12);
+        assert!(multi.attempts_for(0.99) < single.attempts_for(0.99));
+    }
+
+    #[test]
+    fn test_k_of_n_matches() {
+        let p = 1.0 / 16.0;
+        // At least 1 of 4 is the usual "any match" probability
+        assert_eq!(Estimate::at_least(p, 4, 1), Estimate::new(p, 4));
+        // All 4 of 4
+        let all = Estimate::at_least(p, 4, 4);
+        assert!((all.probability() - p.powi(4)).abs() < 1e-15);
+        // 2 of 3: 3·p²(1-p) + p³
+        let two = Estimate::at_least(p, 3, 2);
+        let expected = 3.0 * p * p * (1.0 - p) + p.powi(3);
+        assert!((two.probability() - expected).abs() < 1e-15);
+        assert_eq!(Estimate::at_least(p, 3, 4).probability(), 0.0);
+    }
+
+    #[test]
+    fn test_confidence_is_monotonic() {
+        let estimate = Estimate::new(16f64.powi(-8), 1);
+        let attempts: Vec<u64> = CONFIDENCE_LEVELS
+            .iter()
+            .map(|&c| estimate.attempts_for(c))
+            .collect();
+        assert!(attempts.windows(2).all(|w| w[0] < w[1]));
+        // 99% needs ~4.6x the mean
+        let ratio = attempts[2] as f64 / estimate.mean_attempts();
+        assert!((ratio - 4.605).abs() < 0.01);
+        assert!(estimate.found_by(0) == 0.0);
+    }
+
+    #[test]
+    fn test_certain_and_time() {
+        let estimate = Estimate::new(1.0, 1);
+        assert_eq!(estimate.attempts_for(0.99), 1);
+        assert_eq!(
+            Estimate::time_for(1_000_000, 250_000.0),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(Estimate::time_for(10, 0.0), None);
+    }
+}
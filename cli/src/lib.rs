@@ -1,9 +1,15 @@
 // Core mining library
+pub mod estimate;
+pub mod score;
 pub mod target;
 
 // Re-export for convenience
 pub use target::TargetChecker;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod address;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod dynamic_field;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod mining;
 #[cfg(not(target_arch = "wasm32"))]
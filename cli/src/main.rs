@@ -1,25 +1,42 @@
+mod address;
 mod common;
+mod dynamic_field;
+mod estimate;
 mod mining;
 mod module_order;
+mod move_build;
+mod package_deps;
 mod progress;
+mod score;
 mod server;
 mod target;
 
+use crate::address::mnemonic::MnemonicMode;
+use crate::address::multisig::{MultisigMember, MultisigMode};
+use crate::address::split::{self, SplitKeyMode};
+use crate::address::zklogin::ZkLoginMode;
+use crate::address::{AddressMode, FoundKey, KeyScheme};
 use crate::common::{
-    create_split_tx_template, create_template_from_bytes, create_tx_template, format_large_number,
+    DependencySpec, IndexSpec, MiningGoal, NonceSpec, ObjectIndices, ObjectsSpec, ScoreSpec,
+    StreamSpec, TargetSpec, check_scorable, create_split_tx_template, create_template_from_bytes,
+    create_tx_template, create_upgrade_tx_template, format_large_number, random_start_nonce,
     randomize_gas_budget,
 };
+use crate::dynamic_field::{DynamicFieldMode, KEY_PLACEHOLDER, KeyType};
+use crate::estimate::{CONFIDENCE_LEVELS, Estimate};
 use crate::mining::{
-    CpuExecutor, GasCoinMode, MinerConfig, MinerExecutor, PackageMode, SingleObjectMode,
+    BestSearch, CpuExecutor, CreatedObjectsMode, DigestMode, GasCoinMode, MinerConfig,
+    MinerExecutor, NonceStrategy, ObjectRangeMode, PackageMode, ScoredResult, SearchResult,
+    SearchSpace, SingleObjectMode, creation_index, search,
 };
 use crate::module_order::sort_modules_by_dependency;
-use crate::progress::ProgressDisplay;
+use crate::move_build::{LockEnvironment, PublishedDependency, find_build_dir, load_build_dir};
+use crate::package_deps::defines_init;
+use crate::progress::{ProgressDisplay, format_duration};
 use crate::target::TargetChecker;
 use anyhow::{Context, Result};
 use base64::{Engine as _, engine::general_purpose};
 use clap::{Parser, Subcommand};
-use rand::Rng;
-use rand::rngs::OsRng;
 use std::fs;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -29,6 +46,8 @@ use std::thread;
 use std::time::Duration;
 use sui_sdk::SuiClientBuilder;
 use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::transaction::TransactionData;
+use sui_types::{MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID};
 
 #[derive(Parser, Debug)]
 #[command(name = "sui-id-miner")]
@@ -50,14 +69,34 @@ struct Args {
 enum Commands {
     /// Mine for a Package ID (vanity address for Move package)
     Package {
-        /// Hex prefix to search for (without 0x)
-        #[arg(short, long)]
-        prefix: String,
+        #[command(flatten)]
+        target: TargetSpec,
+
+        #[command(flatten)]
+        score: ScoreSpec,
+
+        #[command(flatten)]
+        stream: StreamSpec,
 
-        /// Path to compiled Move module (.mv files directory or single file)
+        #[command(flatten)]
+        nonce: NonceSpec,
+
+        #[command(flatten)]
+        objects: ObjectsSpec,
+
+        #[command(flatten)]
+        dependencies: DependencySpec,
+
+        /// Move package root, its build/<Pkg>/ directory, a .mv files directory or a single
+        /// .mv file
         #[arg(short, long)]
         module: Option<PathBuf>,
 
+        /// Move.lock environment whose published addresses are used for the dependencies
+        /// (default: the one recorded for the chain of --rpc-url)
+        #[arg(long)]
+        env: Option<String>,
+
         /// Sender address
         #[arg(short, long)]
         sender: String,
@@ -66,9 +105,9 @@ enum Commands {
         #[arg(long, default_value = "100000000")]
         gas_budget: u64,
 
-        /// Gas price
-        #[arg(long, default_value = "1000")]
-        gas_price: u64,
+        /// Gas price (default: the reference gas price of --rpc-url)
+        #[arg(long)]
+        gas_price: Option<u64>,
 
         /// Gas object ID (coin to pay for transaction)
         #[arg(long)]
@@ -86,20 +125,104 @@ enum Commands {
         #[arg(long)]
         export_template: bool,
 
+        /// Dry-run the publish and list the objects it creates with their indices (needs
+        /// --gas-object)
+        #[arg(long, conflicts_with = "export_template")]
+        list_objects: bool,
+
+        /// Use GPU for mining
+        #[arg(long)]
+        gpu: bool,
+    },
+    /// Mine the new package ID of a package upgrade
+    Upgrade {
+        #[command(flatten)]
+        target: TargetSpec,
+
+        #[command(flatten)]
+        score: ScoreSpec,
+
+        #[command(flatten)]
+        stream: StreamSpec,
+
+        #[command(flatten)]
+        nonce: NonceSpec,
+
+        #[command(flatten)]
+        dependencies: DependencySpec,
+
+        /// Upgraded Move package root, its build/<Pkg>/ directory, a .mv files directory or a
+        /// single .mv file
+        #[arg(short, long)]
+        module: Option<PathBuf>,
+
+        /// Move.lock environment whose published addresses are used for the dependencies
+        /// (default: the one recorded for the chain of --rpc-url)
+        #[arg(long)]
+        env: Option<String>,
+
+        /// ID of the package being upgraded (its current version)
+        #[arg(long)]
+        package_id: String,
+
+        /// UpgradeCap object ID of the package
+        #[arg(long)]
+        upgrade_cap: String,
+
+        /// Upgrade policy to authorize (0 = compatible, 128 = additive, 192 = dependency-only)
+        #[arg(long, default_value = "0")]
+        policy: u8,
+
+        /// Sender address (owner of the UpgradeCap)
+        #[arg(short, long)]
+        sender: String,
+
+        /// Gas budget
+        #[arg(long, default_value = "100000000")]
+        gas_budget: u64,
+
+        /// Gas price (default: the reference gas price of --rpc-url)
+        #[arg(long)]
+        gas_price: Option<u64>,
+
+        /// Gas object ID (coin to pay for transaction)
+        #[arg(long)]
+        gas_object: String,
+
+        /// Sui RPC URL
+        #[arg(long, default_value = "https://fullnode.testnet.sui.io:443")]
+        rpc_url: String,
+
+        /// Number of CPU threads to use (default: all cores)
+        #[arg(short, long)]
+        threads: Option<usize>,
+
         /// Use GPU for mining
         #[arg(long)]
         gpu: bool,
     },
     /// Mine for Gas Coin IDs (split gas coin)
     Gas {
-        /// Hex prefix to search for (without 0x)
-        #[arg(short, long)]
-        prefix: String,
+        #[command(flatten)]
+        target: TargetSpec,
+
+        #[command(flatten)]
+        score: ScoreSpec,
+
+        #[command(flatten)]
+        stream: StreamSpec,
+
+        #[command(flatten)]
+        nonce: NonceSpec,
 
         /// Split amounts (comma separated, e.g. 1000000,1000000)
         #[arg(short, long, value_delimiter = ',', num_args = 1..)]
         split_amounts: Vec<u64>,
 
+        /// Only accept transactions where at least this many coins match (k of N)
+        #[arg(long, default_value = "1", conflicts_with = "score")]
+        min_matches: u16,
+
         /// Sender address
         #[arg(short, long)]
         sender: String,
@@ -108,9 +231,9 @@ enum Commands {
         #[arg(long, default_value = "100000000")]
         gas_budget: u64,
 
-        /// Gas price
-        #[arg(long, default_value = "1000")]
-        gas_price: u64,
+        /// Gas price (default: the reference gas price of --rpc-url)
+        #[arg(long)]
+        gas_price: Option<u64>,
 
         /// Gas object ID (coin to pay for transaction)
         #[arg(long)]
@@ -130,25 +253,208 @@ enum Commands {
     },
     /// Mine for a Move Call result ID (generic)
     Move {
-        /// Hex prefix to search for (without 0x)
+        #[command(flatten)]
+        target: TargetSpec,
+
+        #[command(flatten)]
+        score: ScoreSpec,
+
+        #[command(flatten)]
+        stream: StreamSpec,
+
+        #[command(flatten)]
+        nonce: NonceSpec,
+
+        /// Base64 encoded transaction bytes
+        #[arg(long)]
+        tx_base64: String,
+
+        #[command(flatten)]
+        indices: IndexSpec,
+
+        /// Number of CPU threads to use (default: all cores)
+        #[arg(short, long)]
+        threads: Option<usize>,
+
+        /// Use GPU for mining
+        #[arg(long)]
+        gpu: bool,
+    },
+    /// Mine a vanity transaction digest (Base58) for any transaction
+    Digest {
+        /// Base58 prefix the transaction digest must start with
         #[arg(short, long)]
         prefix: String,
 
+        /// Match the prefix case-insensitively
+        #[arg(short = 'i', long)]
+        ignore_case: bool,
+
+        #[command(flatten)]
+        stream: StreamSpec,
+
+        #[command(flatten)]
+        nonce: NonceSpec,
+
         /// Base64 encoded transaction bytes
         #[arg(long)]
         tx_base64: String,
 
-        /// Object index to check (default: 0)
+        /// Number of CPU threads to use (default: all cores)
+        #[arg(short, long)]
+        threads: Option<usize>,
+    },
+    /// Mine a vanity dynamic field ID by searching key values (no transaction needed)
+    DynamicField {
+        #[command(flatten)]
+        target: TargetSpec,
+
+        /// Parent object ID (the UID the field is added to)
+        #[arg(long)]
+        parent: String,
+
+        /// Move type of the key
+        #[arg(long, value_enum, default_value = "u64")]
+        key_type: KeyType,
+
+        /// Key template for string keys, `{}` is replaced by a counter (e.g. "player-{}")
+        #[arg(long, default_value = KEY_PLACEHOLDER)]
+        key: String,
+
+        /// First counter value to try
         #[arg(long, default_value = "0")]
-        object_index: u16,
+        start: u64,
+
+        /// The field is a dynamic object field (`dynamic_object_field::add`)
+        #[arg(long)]
+        object_field: bool,
 
         /// Number of CPU threads to use (default: all cores)
         #[arg(short, long)]
         threads: Option<usize>,
+    },
+    /// Mine a vanity account address (generates a fresh keypair)
+    Address {
+        #[command(flatten)]
+        target: TargetSpec,
 
-        /// Use GPU for mining
+        /// Signature scheme of the generated keypair
+        #[arg(long, value_enum, default_value = "ed25519")]
+        scheme: KeyScheme,
+
+        /// Mine a recoverable BIP-39 seed phrase (12 or 24 words) instead of a raw key
+        #[arg(long, value_name = "WORDS", num_args = 0..=1, default_missing_value = "12")]
+        mnemonic: Option<usize>,
+
+        /// Number of CPU threads to use (default: all cores)
+        #[arg(short, long)]
+        threads: Option<usize>,
+    },
+    /// Mine a vanity multisig address by adding a generated salt member to fixed members
+    Multisig {
+        #[command(flatten)]
+        target: TargetSpec,
+
+        /// Member public keys (Sui base64 flag || public key, as in `sui keytool`)
+        #[arg(long, num_args = 1.., required = true)]
+        pks: Vec<String>,
+
+        /// Member weights, in the same order as --pks
+        #[arg(long, num_args = 1.., required = true)]
+        weights: Vec<u8>,
+
+        /// Signing threshold
         #[arg(long)]
-        gpu: bool,
+        threshold: u16,
+
+        /// Signature scheme of the generated salt member
+        #[arg(long, value_enum, default_value = "ed25519")]
+        scheme: KeyScheme,
+
+        /// Weight of the salt member
+        #[arg(long, default_value = "1")]
+        salt_weight: u8,
+
+        /// Number of CPU threads to use (default: all cores)
+        #[arg(short, long)]
+        threads: Option<usize>,
+    },
+    /// Mine a user salt that gives a vanity zkLogin address
+    Zklogin {
+        #[command(flatten)]
+        target: TargetSpec,
+
+        /// OAuth issuer (`iss` claim), e.g. https://accounts.google.com
+        #[arg(long)]
+        iss: String,
+
+        /// OAuth client ID (`aud` claim)
+        #[arg(long)]
+        aud: String,
+
+        /// Value of the key claim (usually the `sub` claim)
+        #[arg(long)]
+        sub: String,
+
+        /// Name of the key claim
+        #[arg(long, default_value = "sub")]
+        key_claim: String,
+
+        /// Number of CPU threads to use (default: all cores)
+        #[arg(short, long)]
+        threads: Option<usize>,
+    },
+    /// Mine an offset for someone else's public key (split-key vanity address)
+    SplitKey {
+        #[command(flatten)]
+        target: TargetSpec,
+
+        /// The requester's compressed public key (hex or Sui base64, flag optional)
+        #[arg(long)]
+        public_key: String,
+
+        /// Signature scheme of the public key (ed25519 is not supported)
+        #[arg(long, value_enum, default_value = "secp256k1")]
+        scheme: KeyScheme,
+
+        /// Number of CPU threads to use (default: all cores)
+        #[arg(short, long)]
+        threads: Option<usize>,
+    },
+    /// Combine your private key with a mined split-key offset (runs offline)
+    Combine {
+        /// Your private key (suiprivkey, Sui base64 or 32-byte hex)
+        #[arg(long)]
+        private_key: String,
+
+        /// Offset returned by the miner (32-byte hex)
+        #[arg(long)]
+        offset: String,
+
+        /// Signature scheme of the private key
+        #[arg(long, value_enum, default_value = "secp256k1")]
+        scheme: KeyScheme,
+
+        /// Address the miner reported, checked against the combined key
+        #[arg(long)]
+        expect: Option<String>,
+    },
+    /// Estimate the success probability and expected attempts for a target
+    Estimate {
+        #[command(flatten)]
+        target: TargetSpec,
+
+        /// Object IDs checked per attempt (e.g. the number of split coins in gas mode)
+        #[arg(long, default_value = "1")]
+        outputs: u32,
+
+        /// Number of those IDs that must match (k of N)
+        #[arg(long, default_value = "1")]
+        min_matches: u32,
+
+        /// Measured hashrate in attempts per second, to estimate wall-clock time
+        #[arg(long)]
+        hashrate: Option<f64>,
     },
 }
 
@@ -168,8 +474,14 @@ async fn main() -> Result<()> {
 
     match args.command {
         Some(Commands::Package {
-            prefix,
+            target,
+            score,
+            stream,
+            nonce,
+            objects,
+            dependencies,
             module,
+            env,
             sender,
             gas_budget,
             gas_price,
@@ -177,11 +489,18 @@ async fn main() -> Result<()> {
             rpc_url,
             threads,
             export_template,
+            list_objects,
             gpu,
         }) => {
             run_package_mining(
-                prefix,
+                target,
+                score,
+                stream,
+                nonce,
+                objects,
+                dependencies,
                 module,
+                env,
                 sender,
                 gas_budget,
                 gas_price,
@@ -189,13 +508,58 @@ async fn main() -> Result<()> {
                 rpc_url,
                 threads,
                 export_template,
+                list_objects,
+                gpu,
+            )
+            .await
+        }
+        Some(Commands::Upgrade {
+            target,
+            score,
+            stream,
+            nonce,
+            dependencies,
+            module,
+            env,
+            package_id,
+            upgrade_cap,
+            policy,
+            sender,
+            gas_budget,
+            gas_price,
+            gas_object,
+            rpc_url,
+            threads,
+            gpu,
+        }) => {
+            run_upgrade_mining(
+                target,
+                score,
+                stream,
+                nonce,
+                dependencies,
+                module,
+                env,
+                package_id,
+                upgrade_cap,
+                policy,
+                sender,
+                gas_budget,
+                gas_price,
+                gas_object,
+                rpc_url,
+                threads,
                 gpu,
             )
             .await
         }
         Some(Commands::Gas {
-            prefix,
+            target,
+            score,
+            stream,
+            nonce,
             split_amounts,
+            min_matches,
             sender,
             gas_budget,
             gas_price,
@@ -205,8 +569,12 @@ async fn main() -> Result<()> {
             gpu,
         }) => {
             run_gas_mining(
-                prefix,
+                target,
+                score,
+                stream,
+                nonce,
                 split_amounts,
+                min_matches,
                 sender,
                 gas_budget,
                 gas_price,
@@ -218,12 +586,89 @@ async fn main() -> Result<()> {
             .await
         }
         Some(Commands::Move {
-            prefix,
+            target,
+            score,
+            stream,
+            nonce,
             tx_base64,
-            object_index,
+            indices,
             threads,
             gpu,
-        }) => run_move_mining(prefix, tx_base64, object_index, threads, gpu).await,
+        }) => {
+            run_move_mining(
+                target, score, stream, nonce, tx_base64, indices, threads, gpu,
+            )
+            .await
+        }
+        Some(Commands::Digest {
+            prefix,
+            ignore_case,
+            stream,
+            nonce,
+            tx_base64,
+            threads,
+        }) => run_digest_mining(prefix, ignore_case, stream, nonce, tx_base64, threads),
+        Some(Commands::DynamicField {
+            target,
+            parent,
+            key_type,
+            key,
+            start,
+            object_field,
+            threads,
+        }) => run_dynamic_field_mining(target, parent, key_type, key, start, object_field, threads),
+        Some(Commands::Address {
+            target,
+            scheme,
+            mnemonic,
+            threads,
+        }) => match mnemonic {
+            Some(words) => run_mnemonic_mining(target, scheme, words, threads),
+            None => run_address_mining(target, scheme, threads),
+        },
+        Some(Commands::Multisig {
+            target,
+            pks,
+            weights,
+            threshold,
+            scheme,
+            salt_weight,
+            threads,
+        }) => run_multisig_mining(
+            target,
+            pks,
+            weights,
+            threshold,
+            scheme,
+            salt_weight,
+            threads,
+        ),
+        Some(Commands::Zklogin {
+            target,
+            iss,
+            aud,
+            sub,
+            key_claim,
+            threads,
+        }) => run_zklogin_mining(target, iss, aud, sub, key_claim, threads),
+        Some(Commands::SplitKey {
+            target,
+            public_key,
+            scheme,
+            threads,
+        }) => run_split_key_mining(target, public_key, scheme, threads),
+        Some(Commands::Combine {
+            private_key,
+            offset,
+            scheme,
+            expect,
+        }) => run_combine(private_key, offset, scheme, expect),
+        Some(Commands::Estimate {
+            target,
+            outputs,
+            min_matches,
+            hashrate,
+        }) => run_estimate(target, outputs, min_matches, hashrate),
         None => {
             // Default behavior if no subcommand is provided (and not server mode)
             // Print help
@@ -235,37 +680,64 @@ async fn main() -> Result<()> {
 }
 
 async fn run_package_mining(
-    prefix: String,
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
+    mut objects_spec: ObjectsSpec,
+    dependency_spec: DependencySpec,
     module_path: Option<PathBuf>,
+    env: Option<String>,
     sender_str: String,
     gas_budget: u64,
-    gas_price: u64,
+    gas_price: Option<u64>,
     gas_object_str: Option<String>,
     rpc_url: String,
     threads_opt: Option<usize>,
     export_template: bool,
+    list_objects: bool,
     gpu: bool,
 ) -> Result<()> {
-    // Parse and validate prefix
-    let prefix = prefix.trim_start_matches("0x");
-    if !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
-        anyhow::bail!("Invalid prefix: must be hexadecimal characters only");
+    if list_objects && gas_object_str.is_none() {
+        anyhow::bail!("--list-objects dry-runs the publish and needs a real --gas-object");
     }
 
-    let target = TargetChecker::from_hex_prefix(prefix).context("Failed to parse prefix")?;
+    // Parse and validate target pattern (nothing is mined when only listing objects)
+    let goal = (!list_objects)
+        .then(|| MiningGoal::build(&target_spec, &score_spec)?.streaming(&stream_spec))
+        .transpose()
+        .context("Failed to parse target")?;
+    let nonce = nonce_spec.build()?;
+    let extra_targets = objects_spec
+        .build()
+        .context("Failed to parse created-object targets")?;
+    if !extra_targets.is_empty() && matches!(goal, Some(MiningGoal::Best(_))) {
+        anyhow::bail!("--score cannot be combined with extra object or UpgradeCap targets");
+    }
 
-    println!("🚀 Sui Package ID Miner");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("🎯 Target prefix: 0x{}", prefix);
-    println!(
-        "📊 Difficulty: {} hex chars (~{} attempts avg)",
-        target.difficulty(),
-        format_large_number(target.estimated_attempts())
-    );
+    eprintln!("🚀 Sui Package ID Miner");
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    if let Some(goal) = &goal {
+        print_goal(goal);
+    }
+    if objects_spec.object_index != 0 {
+        eprintln!("🔢 Object Index: {}", objects_spec.object_index);
+    }
+    if let Some(MiningGoal::Target(target) | MiningGoal::All { target, .. }) = &goal {
+        if target.probability().is_some() {
+            eprintln!(
+                "📊 Difficulty: {} hex chars (~{} attempts avg)",
+                target.difficulty(),
+                format_large_number(target.estimated_attempts())
+            );
+        } else {
+            eprintln!("📊 Difficulty: unknown (cannot be estimated for this regex)");
+        }
+    }
 
     // Load module bytes
-    let raw_modules = load_module_bytes(&module_path)?;
-    println!(
+    let (raw_modules, published) = load_module_bytes(&module_path, env, &rpc_url).await?;
+    eprintln!(
         "📦 Loaded {} module(s), {} bytes total",
         raw_modules.len(),
         raw_modules.iter().map(|m| m.len()).sum::<usize>()
@@ -273,21 +745,36 @@ async fn run_package_mining(
 
     // Sort modules by dependency order (critical for multi-module packages!)
     let module_bytes = if raw_modules.len() > 1 {
-        println!("🔄 Sorting modules by dependency order...");
+        eprintln!("🔄 Sorting modules by dependency order...");
         sort_modules_by_dependency(raw_modules)?
     } else {
         raw_modules
     };
+    let dependencies =
+        resolve_dependencies(&dependency_spec, &module_path, &module_bytes, &published)?;
+
+    // Objects created by `init` come before the UpgradeCap, so the cap's index is only
+    // known from a dry-run
+    let find_cap = goal.is_some()
+        && !export_template
+        && objects_spec.guesses_cap_index()
+        && defines_init(&module_bytes)?;
+    if find_cap && gas_object_str.is_none() {
+        anyhow::bail!(
+            "The package defines init, which may create objects ahead of the UpgradeCap; \
+             pass --cap-index (see --list-objects) or a real --gas-object to find it"
+        );
+    }
 
     // Parse sender
     let sender = SuiAddress::from_str(&sender_str).context("Invalid sender address")?;
-    println!("👤 Sender: {}", sender);
+    eprintln!("👤 Sender: {}", sender);
 
     // Query gas object from RPC if provided
     let gas_payment = if let Some(gas_id) = &gas_object_str {
-        println!("🔍 Querying gas object from {}...", rpc_url);
-        let gas_ref = get_gas_object_ref(&rpc_url, gas_id).await?;
-        println!(
+        eprintln!("🔍 Querying gas object from {}...", rpc_url);
+        let gas_ref = get_object_ref(&rpc_url, gas_id, "gas object").await?;
+        eprintln!(
             "✅ Gas object: {} (version: {}, digest: {})",
             gas_ref.0,
             gas_ref.1.value(),
@@ -295,7 +782,7 @@ async fn run_package_mining(
         );
         gas_ref
     } else {
-        println!("⚠️  No gas object specified, using mock data");
+        eprintln!("⚠️  No gas object specified, using mock data");
         (
             ObjectID::from_str(
                 "0x0000000000000000000000000000000000000000000000000000000000000000",
@@ -306,29 +793,38 @@ async fn run_package_mining(
     };
 
     // Randomize gas budget using shared logic
-    let (effective_gas_budget, extra_gas) = randomize_gas_budget(gas_budget);
+    let (effective_gas_budget, extra_gas) = randomize_gas_budget(gas_budget, nonce);
     if extra_gas > 0 {
-        println!(
+        eprintln!(
             "Adjusted Gas Budget: {} (Base: {} + Random: {})",
             effective_gas_budget, gas_budget, extra_gas
         );
     }
 
     // Create transaction template with salt placeholder
+    let gas_price = resolve_gas_price(gas_price, nonce, &rpc_url).await?;
     let (tx_template, salt_offset) = create_tx_template(
         sender,
         module_bytes,
+        dependencies,
         effective_gas_budget,
         gas_price,
         gas_payment,
+        nonce,
     )?;
-    println!(
+    eprintln!(
         "📝 Transaction template: {} bytes (salt at offset {})",
         tx_template.len(),
         salt_offset
     );
 
     if export_template {
+        if nonce.max().is_some() {
+            anyhow::bail!(
+                "The Web Miner can't keep the nonce inside the {} window",
+                nonce
+            );
+        }
         println!("\n📤 Export for Web Miner:");
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
         println!("TX_TEMPLATE_HEX={}", hex::encode(&tx_template));
@@ -338,43 +834,191 @@ async fn run_package_mining(
         return Ok(());
     }
 
+    let Some(goal) = goal else {
+        return list_created_objects(&rpc_url, &tx_template).await;
+    };
+
+    if find_cap {
+        let cap_index = find_cap_index(&rpc_url, &tx_template).await?;
+        eprintln!("🧷 UpgradeCap Index: {} (from the dry-run)", cap_index);
+        objects_spec.cap_index = Some(cap_index);
+    }
+    let extra_targets = objects_spec.build()?;
+    for extra in &extra_targets {
+        eprintln!("🧷 Object {} Target: {}", extra.index, extra.target);
+    }
+
+    if objects_spec.object_index != 0 || !extra_targets.is_empty() {
+        start_mining(
+            tx_template,
+            salt_offset,
+            threads_opt,
+            CreatedObjectsMode::new(objects_spec.object_index, extra_targets),
+            goal,
+            nonce,
+            gpu,
+        )
+    } else {
+        start_mining(
+            tx_template,
+            salt_offset,
+            threads_opt,
+            PackageMode,
+            goal,
+            nonce,
+            gpu,
+        )
+    }
+}
+
+async fn run_upgrade_mining(
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
+    dependency_spec: DependencySpec,
+    module_path: Option<PathBuf>,
+    env: Option<String>,
+    package_id_str: String,
+    upgrade_cap_str: String,
+    policy: u8,
+    sender_str: String,
+    gas_budget: u64,
+    gas_price: Option<u64>,
+    gas_object_str: String,
+    rpc_url: String,
+    threads_opt: Option<usize>,
+    gpu: bool,
+) -> Result<()> {
+    let goal = MiningGoal::build(&target_spec, &score_spec)
+        .and_then(|goal| goal.streaming(&stream_spec))
+        .context("Failed to parse target")?;
+    let nonce = nonce_spec.build()?;
+
+    eprintln!("🚀 Sui Package Upgrade ID Miner");
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    print_goal(&goal);
+
+    let package_id = ObjectID::from_str(&package_id_str).context("Invalid package ID")?;
+    let sender = SuiAddress::from_str(&sender_str).context("Invalid sender address")?;
+    eprintln!("📦 Upgrading: {}", package_id);
+    eprintln!("👤 Sender: {}", sender);
+
+    let (raw_modules, published) = load_module_bytes(&module_path, env, &rpc_url).await?;
+    eprintln!(
+        "📦 Loaded {} module(s), {} bytes total",
+        raw_modules.len(),
+        raw_modules.iter().map(|m| m.len()).sum::<usize>()
+    );
+    let module_bytes = if raw_modules.len() > 1 {
+        eprintln!("🔄 Sorting modules by dependency order...");
+        sort_modules_by_dependency(raw_modules)?
+    } else {
+        raw_modules
+    };
+    let dependencies =
+        resolve_dependencies(&dependency_spec, &module_path, &module_bytes, &published)?;
+
+    eprintln!("🔍 Querying UpgradeCap and gas object from {}...", rpc_url);
+    let upgrade_cap = get_object_ref(&rpc_url, &upgrade_cap_str, "UpgradeCap").await?;
+    let gas_payment = get_object_ref(&rpc_url, &gas_object_str, "gas object").await?;
+    eprintln!(
+        "✅ UpgradeCap: {} (version: {})",
+        upgrade_cap.0,
+        upgrade_cap.1.value()
+    );
+    eprintln!(
+        "✅ Gas object: {} (version: {}, digest: {})",
+        gas_payment.0,
+        gas_payment.1.value(),
+        gas_payment.2
+    );
+
+    let (effective_gas_budget, extra_gas) = randomize_gas_budget(gas_budget, nonce);
+    if extra_gas > 0 {
+        eprintln!(
+            "Adjusted Gas Budget: {} (Base: {} + Random: {})",
+            effective_gas_budget, gas_budget, extra_gas
+        );
+    }
+
+    let gas_price = resolve_gas_price(gas_price, nonce, &rpc_url).await?;
+    let (tx_template, salt_offset) = create_upgrade_tx_template(
+        sender,
+        package_id,
+        upgrade_cap,
+        policy,
+        module_bytes,
+        dependencies,
+        effective_gas_budget,
+        gas_price,
+        gas_payment,
+        nonce,
+    )?;
+    eprintln!(
+        "📝 Transaction template: {} bytes (salt at offset {})",
+        tx_template.len(),
+        salt_offset
+    );
+
+    // The new package version is the first object the upgrade creates, like a publish
     start_mining(
         tx_template,
         salt_offset,
         threads_opt,
         PackageMode,
-        target,
-        prefix,
+        goal,
+        nonce,
         gpu,
     )
 }
 
 async fn run_gas_mining(
-    prefix: String,
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
     split_amounts: Vec<u64>,
+    min_matches: u16,
     sender_str: String,
     gas_budget: u64,
-    gas_price: u64,
+    gas_price: Option<u64>,
     gas_object_str: Option<String>,
     rpc_url: String,
     threads_opt: Option<usize>,
     gpu: bool,
 ) -> Result<()> {
-    let prefix = prefix.trim_start_matches("0x");
-    let target = TargetChecker::from_hex_prefix(prefix).context("Failed to parse prefix")?;
-
-    println!("🚀 Sui Gas Coin ID Miner");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("🎯 Target prefix: 0x{}", prefix);
-    println!("💰 Split amounts: {:?}", split_amounts);
+    let goal = MiningGoal::build(&target_spec, &score_spec)
+        .and_then(|goal| goal.streaming(&stream_spec))
+        .context("Failed to parse target")?;
+    let nonce = nonce_spec.build()?;
+
+    eprintln!("🚀 Sui Gas Coin ID Miner");
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    print_goal(&goal);
+    eprintln!("💰 Split amounts: {:?}", split_amounts);
+    if min_matches as usize > split_amounts.len() {
+        anyhow::bail!(
+            "--min-matches {} is more than the {} split coins",
+            min_matches,
+            split_amounts.len()
+        );
+    }
+    if min_matches > 1 {
+        eprintln!(
+            "🎯 Matches required: {} of {}",
+            min_matches,
+            split_amounts.len()
+        );
+    }
 
     let sender = SuiAddress::from_str(&sender_str).context("Invalid sender address")?;
 
     let gas_payment = if let Some(gas_id) = &gas_object_str {
-        println!("🔍 Querying gas object from {}...", rpc_url);
-        get_gas_object_ref(&rpc_url, gas_id).await?
+        eprintln!("🔍 Querying gas object from {}...", rpc_url);
+        get_object_ref(&rpc_url, gas_id, "gas object").await?
     } else {
-        println!("⚠️  No gas object specified, using mock data");
+        eprintln!("⚠️  No gas object specified, using mock data");
         (
             ObjectID::from_str(
                 "0x0000000000000000000000000000000000000000000000000000000000000000",
@@ -384,85 +1028,622 @@ async fn run_gas_mining(
         )
     };
 
-    let (effective_gas_budget, _) = randomize_gas_budget(gas_budget);
+    let (effective_gas_budget, _) = randomize_gas_budget(gas_budget, nonce);
 
+    let gas_price = resolve_gas_price(gas_price, nonce, &rpc_url).await?;
     let (tx_template, salt_offset, num_outputs) = create_split_tx_template(
         sender,
         split_amounts,
         effective_gas_budget,
         gas_price,
         gas_payment,
+        nonce,
     )?;
 
     start_mining(
         tx_template,
         salt_offset,
         threads_opt,
-        GasCoinMode::new(num_outputs),
-        target,
-        prefix,
+        GasCoinMode::new(num_outputs).with_min_matches(min_matches),
+        goal,
+        nonce,
         gpu,
     )
 }
 
 async fn run_move_mining(
-    prefix: String,
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
     tx_base64: String,
-    object_index: u16,
+    index_spec: IndexSpec,
     threads_opt: Option<usize>,
     gpu: bool,
 ) -> Result<()> {
-    let prefix = prefix.trim_start_matches("0x");
-    let target = TargetChecker::from_hex_prefix(prefix).context("Failed to parse prefix")?;
+    let goal = MiningGoal::build(&target_spec, &score_spec)
+        .and_then(|goal| goal.streaming(&stream_spec))
+        .context("Failed to parse target")?;
+    let nonce = nonce_spec.build()?;
 
-    println!("🚀 Sui Move Call ID Miner");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("🎯 Target prefix: 0x{}", prefix);
-    println!("🔢 Object Index: {}", object_index);
+    eprintln!("🚀 Sui Move Call ID Miner");
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    print_goal(&goal);
+    let indices = index_spec.build()?;
+    eprintln!("🔢 Object Index: {}", indices);
 
     let tx_bytes = general_purpose::STANDARD
         .decode(&tx_base64)
         .context("Failed to decode base64 transaction bytes")?;
 
-    let (tx_template, salt_offset) = create_template_from_bytes(&tx_bytes)?;
+    let (tx_template, salt_offset) = create_template_from_bytes(&tx_bytes, nonce)?;
+
+    match indices {
+        ObjectIndices::Single(index) => start_mining(
+            tx_template,
+            salt_offset,
+            threads_opt,
+            SingleObjectMode::new(index),
+            goal,
+            nonce,
+            gpu,
+        ),
+        ObjectIndices::Range(start, end) => start_mining(
+            tx_template,
+            salt_offset,
+            threads_opt,
+            ObjectRangeMode::new(start, end),
+            goal,
+            nonce,
+            gpu,
+        ),
+    }
+}
+
+fn run_digest_mining(
+    prefix: String,
+    ignore_case: bool,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
+    tx_base64: String,
+    threads_opt: Option<usize>,
+) -> Result<()> {
+    let target = TargetChecker::from_base58_prefix(&prefix, ignore_case)
+        .context("Failed to parse Base58 prefix")?;
+    let nonce = nonce_spec.build()?;
+
+    eprintln!("🚀 Sui Transaction Digest Miner");
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    eprintln!("🎯 Target: {}", target);
+
+    let tx_bytes = general_purpose::STANDARD
+        .decode(&tx_base64)
+        .context("Failed to decode base64 transaction bytes")?;
+
+    let (tx_template, salt_offset) = create_template_from_bytes(&tx_bytes, nonce)?;
 
     start_mining(
         tx_template,
         salt_offset,
         threads_opt,
-        SingleObjectMode::new(object_index),
-        target,
-        prefix,
-        gpu,
+        DigestMode,
+        MiningGoal::Target(target).streaming(&stream_spec)?,
+        nonce,
+        false,
     )
 }
 
+fn run_dynamic_field_mining(
+    target_spec: TargetSpec,
+    parent: String,
+    key_type: KeyType,
+    template: String,
+    start: u64,
+    object_field: bool,
+    threads_opt: Option<usize>,
+) -> Result<()> {
+    let target = target_spec.build().context("Failed to parse target")?;
+    let parent = ObjectID::from_str(&parent).context("Invalid parent object ID")?;
+    let mode = DynamicFieldMode::new(parent, key_type, &template, start, object_field)?;
+
+    println!("🚀 Sui Dynamic Field ID Miner");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("🎯 Target: {}", target);
+    println!("🌳 Parent: {}", parent);
+    println!("🔑 Key Type: {}", key_type);
+    if key_type != KeyType::U64 {
+        println!("📝 Key Template: {}", template);
+    }
+
+    let Some(result) = run_search(mode.clone(), &target, threads_opt) else {
+        eprintln!("\n❌ Mining cancelled without finding a match.");
+        return Ok(());
+    };
+
+    let found = result.found;
+    mode.verify(&found)?;
+
+    println!("\n");
+    println!("🎉 ════════════════════════════════════════════════════════");
+    println!("   FOUND MATCHING DYNAMIC FIELD ID!");
+    println!("════════════════════════════════════════════════════════════");
+    println!();
+    println!("📦 Field ID:          {}", found.object_id);
+    println!("🔑 Key:               {}", found.key);
+    println!("🧬 Key (BCS):         0x{}", hex::encode(&found.key_bytes));
+    println!(
+        "🔢 Attempts:          {}",
+        format_large_number(result.attempts)
+    );
+
+    Ok(())
+}
+
+fn run_address_mining(
+    target_spec: TargetSpec,
+    scheme: KeyScheme,
+    threads_opt: Option<usize>,
+) -> Result<()> {
+    let target = target_spec.build().context("Failed to parse target")?;
+
+    println!("🚀 Sui Address Miner");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("🎯 Target: {}", target);
+    println!("🔑 Scheme: {}", scheme);
+
+    let Some(result) = run_search(AddressMode::new(scheme), &target, threads_opt) else {
+        eprintln!("\n❌ Mining cancelled without finding a match.");
+        return Ok(());
+    };
+
+    let key = result.found;
+    key.verify()?;
+
+    println!("\n");
+    println!("🎉 ════════════════════════════════════════════════════════");
+    println!("   FOUND MATCHING ADDRESS!");
+    println!("════════════════════════════════════════════════════════════");
+    println!();
+    print_found_key(&key, result.attempts)?;
+    println!("🗝️  Keystore entry (Base64, for sui.keystore):");
+    println!("{}", key.keystore_base64()?);
+    println!();
+    println!("⚠️  Anyone with this private key controls the address. Store it securely.");
+
+    Ok(())
+}
+
+fn run_mnemonic_mining(
+    target_spec: TargetSpec,
+    scheme: KeyScheme,
+    words: usize,
+    threads_opt: Option<usize>,
+) -> Result<()> {
+    let target = target_spec.build().context("Failed to parse target")?;
+    let mode = MnemonicMode::new(scheme, words)?;
+
+    println!("🚀 Sui Mnemonic Address Miner");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("🎯 Target: {}", target);
+    println!("🔑 Scheme: {} ({})", scheme, scheme.derivation_path());
+    println!("📝 Mnemonic: {} words", words);
+
+    let Some(result) = run_search(mode, &target, threads_opt) else {
+        eprintln!("\n❌ Mining cancelled without finding a match.");
+        return Ok(());
+    };
+
+    let found = result.found;
+    found.key.verify()?;
+
+    println!("\n");
+    println!("🎉 ════════════════════════════════════════════════════════");
+    println!("   FOUND MATCHING ADDRESS!");
+    println!("════════════════════════════════════════════════════════════");
+    println!();
+    print_found_key(&found.key, result.attempts)?;
+    println!("🧭 Derivation Path:   {}", scheme.derivation_path());
+    println!("📝 Seed Phrase:");
+    println!("{}", found.phrase);
+    println!(
+        "   Import with: sui keytool import \"<PHRASE>\" {}",
+        found.key.scheme
+    );
+    println!();
+    println!("⚠️  Anyone with this seed phrase controls the address. Store it securely.");
+
+    Ok(())
+}
+
+fn run_multisig_mining(
+    target_spec: TargetSpec,
+    pks: Vec<String>,
+    weights: Vec<u8>,
+    threshold: u16,
+    scheme: KeyScheme,
+    salt_weight: u8,
+    threads_opt: Option<usize>,
+) -> Result<()> {
+    let target = target_spec.build().context("Failed to parse target")?;
+    if pks.len() != weights.len() {
+        anyhow::bail!(
+            "Got {} public keys but {} weights",
+            pks.len(),
+            weights.len()
+        );
+    }
+    let members = pks
+        .iter()
+        .zip(&weights)
+        .map(|(pk, weight)| MultisigMember::parse(pk, *weight))
+        .collect::<Result<Vec<_>>>()?;
+    let mode = MultisigMode::new(members, threshold, scheme, salt_weight)?;
+
+    println!("🚀 Sui Multisig Address Miner");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("🎯 Target: {}", target);
+    println!(
+        "👥 Members: {} + salt member (weight {}), threshold {}",
+        mode.members.len(),
+        salt_weight,
+        threshold
+    );
+    println!("🔑 Salt Scheme: {}", scheme);
+    if mode.salt_required() {
+        println!("⚠️  The fixed members can't reach the threshold alone: keep the salt key safe!");
+    }
+
+    let Some(result) = run_search(mode.clone(), &target, threads_opt) else {
+        eprintln!("\n❌ Mining cancelled without finding a match.");
+        return Ok(());
+    };
+
+    let found = result.found;
+    let address = mode.verify(&found)?;
+    found.salt_key.verify()?;
+
+    println!("\n");
+    println!("🎉 ════════════════════════════════════════════════════════");
+    println!("   FOUND MATCHING MULTISIG ADDRESS!");
+    println!("════════════════════════════════════════════════════════════");
+    println!();
+    println!("👥 Multisig Address:  {}", address);
+    println!(
+        "🔢 Attempts:          {}",
+        format_large_number(result.attempts)
+    );
+    println!();
+    println!("📋 Multisig config:");
+    println!(
+        "   sui keytool multi-sig-address {}",
+        mode.keytool_args(&found)
+    );
+    println!();
+    println!("🧂 Salt member public key: {}", found.salt_public_key);
+    println!("🔐 Salt member private key (Bech32):");
+    println!("{}", found.salt_key.bech32()?);
+    if mode.salt_required() {
+        println!("⚠️  The salt key is needed to reach the threshold. Store it securely.");
+    } else {
+        println!("ℹ️  The salt key is not needed to reach the threshold and can be discarded.");
+    }
+
+    Ok(())
+}
+
+fn run_zklogin_mining(
+    target_spec: TargetSpec,
+    iss: String,
+    aud: String,
+    sub: String,
+    key_claim: String,
+    threads_opt: Option<usize>,
+) -> Result<()> {
+    let target = target_spec.build().context("Failed to parse target")?;
+    let mode = ZkLoginMode::new(&iss, &aud, &key_claim, &sub)?;
+
+    println!("🚀 Sui zkLogin Address Miner");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("🎯 Target: {}", target);
+    println!("🌐 Issuer: {}", iss);
+    println!("🪪 Claim: {} = {}", key_claim, sub);
+
+    let Some(result) = run_search(mode, &target, threads_opt) else {
+        eprintln!("\n❌ Mining cancelled without finding a match.");
+        return Ok(());
+    };
+
+    let found = result.found;
+    let address = SuiAddress::from_bytes(found.address).context("Invalid address bytes")?;
+
+    println!("\n");
+    println!("🎉 ════════════════════════════════════════════════════════");
+    println!("   FOUND MATCHING ZKLOGIN ADDRESS!");
+    println!("════════════════════════════════════════════════════════════");
+    println!();
+    println!("👤 Address:           {}", address);
+    println!(
+        "🔢 Attempts:          {}",
+        format_large_number(result.attempts)
+    );
+    println!();
+    println!("🧂 User Salt:         {}", found.salt);
+    println!("🌱 Address Seed:      {}", found.address_seed);
+    println!();
+    println!("ℹ️  Configure this salt for the user in your salt service.");
+
+    Ok(())
+}
+
+fn run_split_key_mining(
+    target_spec: TargetSpec,
+    public_key: String,
+    scheme: KeyScheme,
+    threads_opt: Option<usize>,
+) -> Result<()> {
+    let target = target_spec.build().context("Failed to parse target")?;
+    let public_key = split::parse_public_key(scheme, &public_key)?;
+    let mode = SplitKeyMode::new(scheme, public_key)?;
+
+    println!("🚀 Sui Split-Key Address Miner");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("🎯 Target: {}", target);
+    println!("🔑 Scheme: {}", scheme);
+    println!("🧩 Public Key: {}", hex::encode(public_key));
+
+    let Some(result) = run_search(mode, &target, threads_opt) else {
+        eprintln!("\n❌ Mining cancelled without finding a match.");
+        return Ok(());
+    };
+
+    let found = result.found;
+    let address = SuiAddress::from_bytes(found.address).context("Invalid address bytes")?;
+
+    println!("\n");
+    println!("🎉 ════════════════════════════════════════════════════════");
+    println!("   FOUND MATCHING OFFSET!");
+    println!("════════════════════════════════════════════════════════════");
+    println!();
+    println!("👤 Address:           {}", address);
+    println!("🔑 Scheme:            {}", scheme);
+    println!(
+        "🔢 Attempts:          {}",
+        format_large_number(result.attempts)
+    );
+    println!();
+    println!("🧩 Offset:");
+    println!("{}", hex::encode(found.offset));
+    println!();
+    println!("📤 The key owner combines it offline with:");
+    println!(
+        "   sui-id-miner combine --scheme {} --private-key <KEY> --offset {} --expect {}",
+        scheme,
+        hex::encode(found.offset),
+        address
+    );
+    println!();
+    println!("ℹ️  The offset alone does not give control of the address.");
+
+    Ok(())
+}
+
+fn run_combine(
+    private_key: String,
+    offset: String,
+    scheme: KeyScheme,
+    expect: Option<String>,
+) -> Result<()> {
+    let secret = split::parse_private_key(scheme, &private_key)?;
+    let offset = split::parse_offset(&offset)?;
+    let key = split::combine(scheme, &secret, &offset)?;
+
+    if let Some(expect) = expect {
+        let expected = SuiAddress::from_str(&expect).context("Invalid expected address")?;
+        if expected != key.sui_address()? {
+            anyhow::bail!(
+                "Combined key owns {}, not the expected {}. Check the offset and scheme.",
+                key.sui_address()?,
+                expected
+            );
+        }
+        println!("✅ Combined key matches the expected address");
+    }
+
+    println!("👤 Address:           {}", key.sui_address()?);
+    println!("🔑 Scheme:            {}", key.scheme);
+    println!();
+    println!("🔐 Private Key (Bech32):");
+    println!("{}", key.bech32()?);
+    println!("   Import with: sui keytool import <KEY> {}", key.scheme);
+    println!("🗝️  Keystore entry (Base64, for sui.keystore):");
+    println!("{}", key.keystore_base64()?);
+    println!();
+    println!("⚠️  Anyone with this private key controls the address. Store it securely.");
+
+    Ok(())
+}
+
+fn print_found_key(key: &FoundKey, attempts: u64) -> Result<()> {
+    println!("👤 Address:           {}", key.sui_address()?);
+    println!("🔑 Scheme:            {}", key.scheme);
+    println!("🔢 Attempts:          {}", format_large_number(attempts));
+    println!();
+    println!("🔐 Private Key (Bech32):");
+    println!("{}", key.bech32()?);
+    println!("   Import with: sui keytool import <KEY> {}", key.scheme);
+    Ok(())
+}
+
+/// Run a `SearchSpace` with the same cancellation and progress display as nonce mining
+fn run_search<S: SearchSpace>(
+    space: S,
+    target: &TargetChecker,
+    threads_opt: Option<usize>,
+) -> Option<SearchResult<S::Found>> {
+    let threads = threads_opt.unwrap_or_else(num_cpus::get);
+    println!("🧵 Threads: {}", threads);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let cancel = Arc::new(AtomicBool::new(false));
+
+    let cancel_clone = cancel.clone();
+    ctrlc::set_handler(move || {
+        println!("\n⛔ Cancelled by user");
+        cancel_clone.store(true, Ordering::SeqCst);
+    })
+    .ok();
+
+    let stop_progress = Arc::new(AtomicBool::new(false));
+    let progress = ProgressDisplay::new(Estimate::for_target(target, 1), &target.to_string());
+    let progress_handle = {
+        let stop_progress = stop_progress.clone();
+        let total_attempts = total_attempts.clone();
+        thread::spawn(move || {
+            while !stop_progress.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(100));
+                progress.update(total_attempts.load(Ordering::Relaxed));
+            }
+        })
+    };
+
+    println!("💻 Starting CPU mining...\n");
+    let result = search(space, threads, target, total_attempts, cancel);
+
+    stop_progress.store(true, Ordering::SeqCst);
+    let _ = progress_handle.join();
+    result
+}
+
+fn run_estimate(
+    target_spec: TargetSpec,
+    outputs: u32,
+    min_matches: u32,
+    hashrate: Option<f64>,
+) -> Result<()> {
+    let target = target_spec.build().context("Failed to parse target")?;
+    if outputs == 0 {
+        anyhow::bail!("outputs must be at least 1");
+    }
+    if min_matches > outputs {
+        anyhow::bail!("min-matches can't be more than outputs");
+    }
+    let estimate = Estimate::for_target_at_least(&target, outputs, min_matches)
+        .context("The success probability of this target cannot be computed")?;
+
+    println!("🎯 Target: {}", target);
+    println!("🔢 IDs per attempt: {}", outputs);
+    if min_matches > 1 {
+        println!("🎯 Matches required: {} of {}", min_matches, outputs);
+    }
+    println!(
+        "📊 Per-attempt probability: {:.3e} (1 in {})",
+        estimate.probability(),
+        format_large_number(estimate.mean_attempts().round() as u64)
+    );
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    for confidence in CONFIDENCE_LEVELS {
+        let attempts = estimate.attempts_for(confidence);
+        let time = hashrate
+            .and_then(|rate| Estimate::time_for(attempts, rate))
+            .map(|time| format!(" (~{})", format_duration(time)))
+            .unwrap_or_default();
+        println!(
+            "{:>3.0}% chance: {} attempts{}",
+            confidence * 100.0,
+            format_large_number(attempts),
+            time
+        );
+    }
+    Ok(())
+}
+
+fn print_goal(goal: &MiningGoal) {
+    match goal {
+        MiningGoal::Target(target) => eprintln!("🎯 Target: {}", target),
+        MiningGoal::Best(search) => {
+            eprintln!("🏆 Score: {}", search);
+            let mut limits = Vec::new();
+            if let Some(max_time) = search.budget.max_time {
+                limits.push(format!("{}s", max_time.as_secs()));
+            }
+            if let Some(max_attempts) = search.budget.max_attempts {
+                limits.push(format!("{} attempts", format_large_number(max_attempts)));
+            }
+            if limits.is_empty() {
+                eprintln!("⏱️  Budget: until Ctrl+C");
+            } else {
+                eprintln!("⏱️  Budget: {}", limits.join(" or "));
+            }
+        }
+        MiningGoal::All { target, count } => {
+            eprintln!("🎯 Target: {}", target);
+            match count {
+                Some(count) => eprintln!("🔁 Streaming matches: stop after {}", count),
+                None => eprintln!("🔁 Streaming matches: until Ctrl+C"),
+            }
+        }
+    }
+}
+
 fn start_mining<M: crate::mining::mode::MiningMode>(
     tx_template: Vec<u8>,
     salt_offset: usize,
     threads_opt: Option<usize>,
     mode: M,
-    target: TargetChecker,
-    prefix: &str,
+    goal: MiningGoal,
+    nonce: NonceStrategy,
     gpu: bool,
 ) -> Result<()> {
+    if nonce != NonceStrategy::Epoch {
+        eprintln!("🎲 Nonce: {}", nonce);
+    }
+    let target = match goal {
+        MiningGoal::Target(target) => target,
+        MiningGoal::Best(search) => {
+            return start_best_mining(
+                tx_template,
+                salt_offset,
+                threads_opt,
+                mode,
+                search,
+                nonce,
+                gpu,
+            );
+        }
+        MiningGoal::All { target, count } => {
+            return start_stream_mining(
+                tx_template,
+                salt_offset,
+                threads_opt,
+                mode,
+                target,
+                count,
+                nonce,
+                gpu,
+            );
+        }
+    };
+
     let threads = threads_opt.unwrap_or_else(num_cpus::get);
     if !gpu {
-        println!("🧵 Threads: {}", threads);
+        eprintln!("🧵 Threads: {}", threads);
     }
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
     let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
     let cancel = Arc::new(AtomicBool::new(false));
 
     let cancel_clone = cancel.clone();
     ctrlc::set_handler(move || {
-        println!("\n⛔ Cancelled by user");
+        eprintln!("\n⛔ Cancelled by user");
         cancel_clone.store(true, Ordering::SeqCst);
     })
     .ok();
 
-    let progress = ProgressDisplay::new(target.estimated_attempts(), prefix);
+    let estimate = mode.estimate(&target);
+    let matches_digest = mode.matches_digest();
+    let progress = ProgressDisplay::new(estimate, &target.to_string());
     let progress_handle = {
         let cancel = cancel.clone();
         let total_attempts = total_attempts.clone();
@@ -475,15 +1656,16 @@ fn start_mining<M: crate::mining::mode::MiningMode>(
         })
     };
 
-    let mut rng = OsRng;
-    let start_epoch = rng.gen_range(100_000..(u64::MAX - 1_000_000_000));
-    println!(
-        "💻 Starting {} mining... (Start Epoch: {})\n",
+    let start_nonce = random_start_nonce(nonce);
+    eprintln!(
+        "💻 Starting {} mining... (Start Nonce: {})\n",
         if gpu { "GPU" } else { "CPU" },
-        format_large_number(start_epoch)
+        format_large_number(start_nonce)
     );
 
-    let config = MinerConfig::new(tx_template, salt_offset, threads).with_start_nonce(start_epoch);
+    let config = MinerConfig::new(tx_template, salt_offset, threads)
+        .with_start_nonce(start_nonce)
+        .with_nonce_strategy(nonce);
 
     let result = if gpu {
         #[cfg(feature = "gpu")]
@@ -512,6 +1694,8 @@ fn start_mining<M: crate::mining::mode::MiningMode>(
         )
     };
 
+    // Without a cancel, the executor only stops once a windowed nonce has no values left
+    let exhausted = nonce.max().filter(|_| !cancel.load(Ordering::SeqCst));
     cancel.store(true, Ordering::SeqCst);
     let _ = progress_handle.join();
 
@@ -521,31 +1705,391 @@ fn start_mining<M: crate::mining::mode::MiningMode>(
         println!("   FOUND MATCHING ID!");
         println!("════════════════════════════════════════════════════════════");
         println!();
-        println!(
-            "📦 Object ID:         0x{}",
-            hex::encode(result.object_id.as_ref())
-        );
+        if !matches_digest {
+            println!(
+                "📦 Object ID:         0x{}",
+                hex::encode(result.object_id.as_ref())
+            );
+        }
         println!("📋 Transaction Digest: {}", result.tx_digest);
-        println!("🔢 Index:             {}", result.object_index);
+        if !matches_digest {
+            println!("🔢 Index:             {}", result.object_index);
+        }
+        if target.pattern_count() > 1 {
+            println!(
+                "🎯 Matched Pattern:   {}",
+                target.pattern_label(result.pattern_index)
+            );
+        }
+        if result.matches.len() > 1 {
+            println!("🧩 All Matches:");
+            for matched in &result.matches {
+                println!(
+                    "   [{}] 0x{}",
+                    matched.object_index,
+                    hex::encode(matched.object_id.as_ref())
+                );
+            }
+        }
         println!(
             "🔢 Attempts:          {}",
             format_large_number(result.attempts)
         );
-        println!("Gas Budget Used:    {}", result.gas_budget_used);
+        println!("🎲 {}: {}", nonce.label(), result.nonce_value);
         println!();
         println!("📤 Transaction Bytes (Base64):");
         println!("────────────────────────────────────────────────────────────");
         println!("{}", general_purpose::STANDARD.encode(&result.tx_bytes));
         println!("────────────────────────────────────────────────────────────");
+    } else if let Some(max) = exhausted {
+        eprintln!(
+            "\n❌ No match: every {} up to {} was tried.",
+            nonce.label().to_lowercase(),
+            max
+        );
     } else {
-        println!("\n❌ Mining cancelled without finding a match.");
+        eprintln!("\n❌ Mining cancelled without finding a match.");
+    }
+
+    Ok(())
+}
+
+fn start_best_mining<M: crate::mining::mode::MiningMode>(
+    tx_template: Vec<u8>,
+    salt_offset: usize,
+    threads_opt: Option<usize>,
+    mode: M,
+    search: BestSearch,
+    nonce: NonceStrategy,
+    gpu: bool,
+) -> Result<()> {
+    if gpu {
+        anyhow::bail!("Score mining is only supported on CPU");
+    }
+    check_scorable(&mode)?;
+
+    let threads = threads_opt.unwrap_or_else(num_cpus::get);
+    eprintln!("🧵 Threads: {}", threads);
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let cancel = Arc::new(AtomicBool::new(false));
+
+    let cancel_clone = cancel.clone();
+    ctrlc::set_handler(move || {
+        eprintln!("\n⛔ Stopping, collecting best results...");
+        cancel_clone.store(true, Ordering::SeqCst);
+    })
+    .ok();
+
+    let progress = Arc::new(ProgressDisplay::new(None, &search.to_string()));
+    let stop_progress = Arc::new(AtomicBool::new(false));
+    let progress_handle = {
+        let stop_progress = stop_progress.clone();
+        let total_attempts = total_attempts.clone();
+        let progress = progress.clone();
+        thread::spawn(move || {
+            while !stop_progress.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(100));
+                let attempts = total_attempts.load(Ordering::Relaxed);
+                progress.update(attempts);
+            }
+        })
+    };
+
+    let start_nonce = random_start_nonce(nonce);
+    eprintln!(
+        "💻 Starting CPU score mining... (Start Nonce: {})\n",
+        format_large_number(start_nonce)
+    );
+
+    let config = MinerConfig::new(tx_template, salt_offset, threads)
+        .with_start_nonce(start_nonce)
+        .with_nonce_strategy(nonce);
+
+    let on_improve = {
+        let progress = progress.clone();
+        move |scored: &ScoredResult| {
+            let object_id = format!("0x{}", hex::encode(scored.result.object_id.as_ref()));
+            progress.report_best(
+                &format!("{} (score {})", object_id, scored.score),
+                &format!(
+                    "⬆️  Score {} → {} (index {}, after {} attempts)",
+                    scored.score,
+                    object_id,
+                    scored.result.object_index,
+                    format_large_number(scored.result.attempts)
+                ),
+            );
+        }
+    };
+
+    let executor = CpuExecutor::new();
+    let results = executor.mine_best(
+        mode,
+        &config,
+        &search,
+        total_attempts.clone(),
+        cancel.clone(),
+        on_improve,
+    );
+
+    stop_progress.store(true, Ordering::SeqCst);
+    let _ = progress_handle.join();
+
+    if results.is_empty() {
+        eprintln!("\n❌ Mining stopped before any ID was scored.");
+        return Ok(());
+    }
+
+    println!("\n");
+    println!("🏆 ════════════════════════════════════════════════════════");
+    println!("   BEST {} IDS", results.len());
+    println!("════════════════════════════════════════════════════════════");
+    println!(
+        "🔢 Attempts:          {}",
+        format_large_number(total_attempts.load(Ordering::Relaxed))
+    );
+    for (rank, scored) in results.iter().enumerate() {
+        let result = &scored.result;
+        println!();
+        println!("#{} — score {}", rank + 1, scored.score);
+        println!(
+            "📦 Object ID:         0x{}",
+            hex::encode(result.object_id.as_ref())
+        );
+        println!("📋 Transaction Digest: {}", result.tx_digest);
+        println!("🔢 Index:             {}", result.object_index);
+        println!("🎲 {}: {}", nonce.label(), result.nonce_value);
+        println!("📤 Transaction Bytes (Base64):");
+        println!("{}", general_purpose::STANDARD.encode(&result.tx_bytes));
+    }
+
+    Ok(())
+}
+
+fn start_stream_mining<M: crate::mining::mode::MiningMode>(
+    tx_template: Vec<u8>,
+    salt_offset: usize,
+    threads_opt: Option<usize>,
+    mode: M,
+    target: TargetChecker,
+    count: Option<u64>,
+    nonce: NonceStrategy,
+    gpu: bool,
+) -> Result<()> {
+    if gpu {
+        anyhow::bail!("Streaming every match is only supported on CPU");
+    }
+
+    let threads = threads_opt.unwrap_or_else(num_cpus::get);
+    eprintln!("🧵 Threads: {}", threads);
+    eprintln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let cancel = Arc::new(AtomicBool::new(false));
+
+    let cancel_clone = cancel.clone();
+    ctrlc::set_handler(move || {
+        eprintln!("\n⛔ Stopping...");
+        cancel_clone.store(true, Ordering::SeqCst);
+    })
+    .ok();
+
+    let progress = Arc::new(ProgressDisplay::new(
+        mode.estimate(&target),
+        &target.to_string(),
+    ));
+    let stop_progress = Arc::new(AtomicBool::new(false));
+    let progress_handle = {
+        let stop_progress = stop_progress.clone();
+        let total_attempts = total_attempts.clone();
+        let progress = progress.clone();
+        thread::spawn(move || {
+            while !stop_progress.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(100));
+                let attempts = total_attempts.load(Ordering::Relaxed);
+                progress.update(attempts);
+            }
+        })
+    };
+
+    let start_nonce = random_start_nonce(nonce);
+    eprintln!(
+        "💻 Starting CPU mining, one JSON line per match... (Start Nonce: {})\n",
+        format_large_number(start_nonce)
+    );
+
+    let config = MinerConfig::new(tx_template, salt_offset, threads)
+        .with_start_nonce(start_nonce)
+        .with_nonce_strategy(nonce);
+
+    // One JSON object per line on stdout; everything else goes to stderr
+    let on_match = {
+        let progress = progress.clone();
+        let found = std::sync::atomic::AtomicU64::new(0);
+        let matches_digest = mode.matches_digest();
+        let target = target.clone();
+        move |result: &crate::mining::mode::MiningResult| {
+            let mut line = serde_json::json!({
+                "tx_digest": result.tx_digest.to_string(),
+                "matched_pattern": target.pattern_label(result.pattern_index),
+                "attempts": result.attempts,
+                "nonce_field": nonce.label(),
+                "nonce_value": result.nonce_value,
+                "tx_bytes_base64": general_purpose::STANDARD.encode(&result.tx_bytes),
+            });
+            if !matches_digest {
+                line["object_id"] = format!("0x{}", hex::encode(result.object_id.as_ref())).into();
+                line["object_index"] = result.object_index.into();
+                line["matches"] = result
+                    .matches
+                    .iter()
+                    .map(|m| {
+                        serde_json::json!({
+                            "object_id": format!("0x{}", hex::encode(m.object_id.as_ref())),
+                            "object_index": m.object_index,
+                        })
+                    })
+                    .collect();
+            }
+            let found = found.fetch_add(1, Ordering::Relaxed) + 1;
+            progress.report_match(&line.to_string(), found);
+        }
+    };
+
+    let executor = CpuExecutor::new();
+    let found = executor.mine_all(
+        mode,
+        &config,
+        &target,
+        count,
+        total_attempts.clone(),
+        cancel.clone(),
+        on_match,
+    );
+
+    stop_progress.store(true, Ordering::SeqCst);
+    let _ = progress_handle.join();
+
+    eprintln!(
+        "\n✅ {} match(es) in {} attempts",
+        found,
+        format_large_number(total_attempts.load(Ordering::Relaxed))
+    );
+    // Below the count and not cancelled means a windowed nonce ran out of values
+    if let Some(max) = nonce.max()
+        && !cancel.load(Ordering::SeqCst)
+        && count.is_none_or(|count| found < count)
+    {
+        eprintln!(
+            "🔚 Every {} up to {} was tried.",
+            nonce.label().to_lowercase(),
+            max
+        );
     }
 
     Ok(())
 }
 
-fn load_module_bytes(path: &Option<PathBuf>) -> Result<Vec<Vec<u8>>> {
-    match path {
+/// Gas price used without --gas-price when the reference price can't be queried
+const DEFAULT_GAS_PRICE: u64 = 1000;
+
+/// `--gas-price`, or else the reference gas price of `rpc_url`'s network
+///
+/// Gas price nonces search upward from this price, so they need the real reference price.
+async fn resolve_gas_price(
+    gas_price: Option<u64>,
+    nonce: NonceStrategy,
+    rpc_url: &str,
+) -> Result<u64> {
+    if let Some(gas_price) = gas_price {
+        return Ok(gas_price);
+    }
+
+    let reference = async {
+        let sui_client = SuiClientBuilder::default()
+            .build(rpc_url)
+            .await
+            .context("Failed to connect to Sui RPC")?;
+        sui_client
+            .read_api()
+            .get_reference_gas_price()
+            .await
+            .context("Failed to query the reference gas price")
+    }
+    .await;
+
+    match reference {
+        Ok(price) => {
+            eprintln!("⛽ Reference gas price: {}", price);
+            Ok(price)
+        }
+        Err(e) if matches!(nonce, NonceStrategy::GasPrice { .. }) => {
+            Err(e.context("Gas price nonces start at the reference price, pass --gas-price"))
+        }
+        Err(e) => {
+            eprintln!("⚠️  {:#}, using gas price {}", e, DEFAULT_GAS_PRICE);
+            Ok(DEFAULT_GAS_PRICE)
+        }
+    }
+}
+
+/// Dependency package IDs for publishing or upgrading to the loaded modules
+fn resolve_dependencies(
+    dependency_spec: &DependencySpec,
+    module_path: &Option<PathBuf>,
+    module_bytes: &[Vec<u8>],
+    published: &[PublishedDependency],
+) -> Result<Vec<ObjectID>> {
+    // Mock modules aren't bytecode, so they only get the framework packages
+    let dependencies = match module_path {
+        Some(_) => dependency_spec.build(module_bytes, published)?,
+        None => vec![MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID],
+    };
+    eprintln!(
+        "🔗 Dependencies: {}",
+        dependencies
+            .iter()
+            .map(|id| id.to_hex_literal())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    Ok(dependencies)
+}
+
+/// Load a package's module bytecode and, for a build directory, its locked dependencies in
+/// the Move.lock environment of `rpc_url`'s chain (or `env`)
+async fn load_module_bytes(
+    path: &Option<PathBuf>,
+    env: Option<String>,
+    rpc_url: &str,
+) -> Result<(Vec<Vec<u8>>, Vec<PublishedDependency>)> {
+    if let Some(build_dir) = path.as_deref().map(find_build_dir).transpose()?.flatten() {
+        eprintln!("📂 Reading build output: {}", build_dir.display());
+        let env = lock_environment(env, rpc_url).await?;
+        let package = load_build_dir(&build_dir, Some(&env))?;
+        eprintln!(
+            "   📦 Package {}: {} module(s), {} locked dependencies ({})",
+            package.name,
+            package.modules.len(),
+            package.dependencies.len(),
+            env
+        );
+        for dep in package
+            .dependencies
+            .iter()
+            .filter(|dep| dep.published_at.is_none())
+        {
+            eprintln!(
+                "⚠️  Dependency {} has no published address for {}, pass it with --dependencies",
+                dep.name, env
+            );
+        }
+        return Ok((package.modules, package.dependencies));
+    }
+
+    let modules = match path {
         Some(p) if p.is_dir() => {
             let mut entries: Vec<_> = fs::read_dir(p)?
                 .filter_map(|e| e.ok())
@@ -573,17 +2117,17 @@ fn load_module_bytes(path: &Option<PathBuf>) -> Result<Vec<Vec<u8>>> {
                 anyhow::bail!("No .mv files found in directory");
             }
 
-            println!(
+            eprintln!(
                 "   📦 Loaded {} module(s) (sorted by filename)",
                 modules.len()
             );
-            Ok(modules)
+            modules
         }
         Some(p) if p.is_file() => {
-            println!("📄 Loading module from: {}", p.display());
+            eprintln!("📄 Loading module from: {}", p.display());
             let bytes = fs::read(p)?;
-            println!("   Read {} bytes", bytes.len());
-            Ok(vec![bytes])
+            eprintln!("   Read {} bytes", bytes.len());
+            vec![bytes]
         }
         Some(p) => {
             anyhow::bail!(
@@ -592,32 +2136,151 @@ fn load_module_bytes(path: &Option<PathBuf>) -> Result<Vec<Vec<u8>>> {
             );
         }
         None => {
-            println!("⚠️  No module path specified, using mock data for testing");
-            Ok(vec![vec![0u8; 100]])
+            eprintln!("⚠️  No module path specified, using mock data for testing");
+            vec![vec![0u8; 100]]
+        }
+    };
+    Ok((modules, Vec::new()))
+}
+
+/// The Move.lock environment for `rpc_url`'s chain, or `env` checked against that chain
+///
+/// The chain decides which published addresses a publish must link against, so `env` is
+/// only needed when the chain ID can't be queried.
+async fn lock_environment(env: Option<String>, rpc_url: &str) -> Result<LockEnvironment> {
+    let chain_id = async {
+        let sui_client = SuiClientBuilder::default()
+            .build(rpc_url)
+            .await
+            .context("Failed to connect to Sui RPC")?;
+        sui_client
+            .read_api()
+            .get_chain_identifier()
+            .await
+            .context("Failed to query the chain ID")
+    }
+    .await;
+
+    let chain_id = match chain_id {
+        Ok(chain_id) => Some(chain_id),
+        Err(e) if env.is_some() => {
+            eprintln!("⚠️  Can't check --env against {}: {:#}", rpc_url, e);
+            None
+        }
+        Err(e) => {
+            return Err(e.context(format!(
+                "Move.lock environment for {} unknown, pass --env",
+                rpc_url
+            )));
+        }
+    };
+    Ok(LockEnvironment {
+        name: env,
+        chain_id,
+    })
+}
+
+/// Creation indices searched for each created object
+///
+/// Objects that `init` creates and then wraps or deletes use up indices without showing up
+/// as created, so the object changes don't bound the index; deriving an ID is cheap.
+const MAX_CREATION_INDEX: u16 = 1024;
+
+/// Dry-run a publish template and return every object it creates with its creation index
+async fn dry_run_created_objects(
+    rpc_url: &str,
+    tx_template: &[u8],
+) -> Result<Vec<(u16, sui_sdk::rpc_types::ObjectChange)>> {
+    use sui_sdk::rpc_types::ObjectChange;
+
+    let tx_data: TransactionData =
+        bcs::from_bytes(tx_template).context("Failed to decode transaction template")?;
+    let tx_digest = tx_data.digest();
+
+    eprintln!("🔍 Dry-running the publish on {}...", rpc_url);
+    let sui_client = SuiClientBuilder::default()
+        .build(rpc_url)
+        .await
+        .context("Failed to connect to Sui RPC")?;
+    let response = sui_client
+        .read_api()
+        .dry_run_transaction_block(tx_data)
+        .await
+        .context("Failed to dry-run the publish")?;
+
+    let mut objects = Vec::new();
+    for change in response.object_changes {
+        let object_id = match &change {
+            ObjectChange::Published { package_id, .. } => *package_id,
+            ObjectChange::Created { object_id, .. } => *object_id,
+            _ => continue,
+        };
+        let index = creation_index(&tx_digest, &object_id, MAX_CREATION_INDEX)
+            .context("Created object ID is not derived from the transaction digest")?;
+        objects.push((index, change));
+    }
+    objects.sort_by_key(|(index, _)| *index);
+    Ok(objects)
+}
+
+/// Dry-run a publish template and print every object it creates with its creation index
+async fn list_created_objects(rpc_url: &str, tx_template: &[u8]) -> Result<()> {
+    use sui_sdk::rpc_types::ObjectChange;
+
+    let objects = dry_run_created_objects(rpc_url, tx_template).await?;
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("📋 Created objects (use the index with --object-index or --object):");
+    for (index, change) in objects {
+        match change {
+            ObjectChange::Published { .. } => println!("  [{}] package", index),
+            ObjectChange::Created {
+                object_type, owner, ..
+            } => println!("  [{}] {} ({})", index, object_type, owner),
+            _ => {}
         }
     }
+    Ok(())
+}
+
+/// Dry-run a publish template and return the creation index of its UpgradeCap
+async fn find_cap_index(rpc_url: &str, tx_template: &[u8]) -> Result<u16> {
+    use sui_sdk::rpc_types::ObjectChange;
+    use sui_types::move_package::UpgradeCap;
+
+    dry_run_created_objects(rpc_url, tx_template)
+        .await?
+        .into_iter()
+        .find_map(|(index, change)| match change {
+            ObjectChange::Created { object_type, .. } if object_type == UpgradeCap::type_() => {
+                Some(index)
+            }
+            _ => None,
+        })
+        .context("The dry-run publish created no UpgradeCap")
 }
 
-async fn get_gas_object_ref(
+async fn get_object_ref(
     rpc_url: &str,
     object_id: &str,
+    what: &str,
 ) -> Result<(ObjectID, SequenceNumber, ObjectDigest)> {
     let sui_client = SuiClientBuilder::default()
         .build(rpc_url)
         .await
         .context("Failed to connect to Sui RPC")?;
 
-    let object_id = ObjectID::from_str(object_id).context("Invalid gas object ID")?;
+    let object_id =
+        ObjectID::from_str(object_id).with_context(|| format!("Invalid {} ID", what))?;
 
     let object = sui_client
         .read_api()
         .get_object_with_options(object_id, sui_sdk::rpc_types::SuiObjectDataOptions::new())
         .await
-        .context("Failed to query gas object")?;
+        .with_context(|| format!("Failed to query {}", what))?;
 
     let obj_ref = object
         .object_ref_if_exists()
-        .context("Gas object not found on chain")?;
+        .with_context(|| format!("{} {} not found on chain", what, object_id))?;
 
     Ok(obj_ref)
 }
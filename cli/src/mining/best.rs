@@ -0,0 +1,136 @@
+//! Best-so-far mining - Keep the top-scoring IDs within a budget
+
+use crate::mining::mode::MiningResult;
+use crate::score::Scorer;
+
+use std::fmt;
+use std::time::Duration;
+
+/// When a best-so-far search stops (it also stops when cancelled)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchBudget {
+    pub max_time: Option<Duration>,
+    pub max_attempts: Option<u64>,
+}
+
+/// Configuration for a best-so-far search
+#[derive(Clone, Debug)]
+pub struct BestSearch {
+    pub scorer: Scorer,
+    /// Number of best IDs to keep
+    pub top_k: usize,
+    pub budget: SearchBudget,
+}
+
+impl BestSearch {
+    pub fn new(scorer: Scorer, top_k: usize) -> Self {
+        Self {
+            scorer,
+            top_k: top_k.max(1),
+            budget: SearchBudget::default(),
+        }
+    }
+
+    pub fn with_budget(mut self, budget: SearchBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+}
+
+impl fmt::Display for BestSearch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "best {} by {}", self.top_k, self.scorer)
+    }
+}
+
+/// A mined ID together with its score
+#[derive(Clone, Debug)]
+pub struct ScoredResult {
+    pub score: u32,
+    pub result: MiningResult,
+}
+
+/// Highest-scoring entries seen so far, best first
+///
+/// Entries with equal scores keep their insertion order, so the earliest find wins ties.
+#[derive(Clone, Debug)]
+pub struct TopK<T> {
+    capacity: usize,
+    entries: Vec<(u32, T)>,
+}
+
+impl<T> TopK<T> {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            entries: Vec::with_capacity(capacity + 1),
+        }
+    }
+
+    /// Lowest score that would still be kept by `insert`
+    pub fn threshold(&self) -> u32 {
+        if self.entries.len() < self.capacity {
+            0
+        } else {
+            self.entries
+                .last()
+                .map_or(0, |(score, _)| score.saturating_add(1))
+        }
+    }
+
+    /// Score of the current best entry
+    pub fn best_score(&self) -> Option<u32> {
+        self.entries.first().map(|(score, _)| *score)
+    }
+
+    /// Insert an entry, returns true if it was kept
+    pub fn insert(&mut self, score: u32, item: T) -> bool {
+        if score < self.threshold() {
+            return false;
+        }
+        let pos = self.entries.partition_point(|(s, _)| *s >= score);
+        self.entries.insert(pos, (score, item));
+        self.entries.truncate(self.capacity);
+        true
+    }
+
+    pub fn into_vec(self) -> Vec<(u32, T)> {
+        self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_k_keeps_best_in_order() {
+        let mut top = TopK::new(3);
+        for (score, name) in [(2, "a"), (5, "b"), (1, "c"), (4, "d"), (3, "e")] {
+            top.insert(score, name);
+        }
+        assert_eq!(top.into_vec(), vec![(5, "b"), (4, "d"), (3, "e")]);
+    }
+
+    #[test]
+    fn test_top_k_threshold_and_ties() {
+        let mut top = TopK::new(2);
+        assert_eq!(top.threshold(), 0);
+        assert!(top.insert(3, "first"));
+        assert!(top.insert(3, "second"));
+        assert_eq!(top.threshold(), 4);
+        // Equal scores don't displace earlier finds
+        assert!(!top.insert(3, "third"));
+        assert!(top.insert(7, "best"));
+        assert_eq!(top.best_score(), Some(7));
+        assert_eq!(top.into_vec(), vec![(7, "best"), (3, "first")]);
+    }
+
+    #[test]
+    fn test_best_search_needs_one_slot() {
+        let search = BestSearch::new(Scorer::LeadingZeros, 0);
+        assert_eq!(search.top_k, 1);
+        assert_eq!(search.to_string(), "best 1 by leading zeros");
+    }
+}
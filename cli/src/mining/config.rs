@@ -1,5 +1,7 @@
 //! Mining configuration
 
+use crate::mining::nonce::NonceStrategy;
+
 /// Configuration for mining operations
 #[derive(Clone, Debug)]
 pub struct MinerConfig {
@@ -11,6 +13,8 @@ pub struct MinerConfig {
     pub threads: usize,
     /// Starting nonce value
     pub start_nonce: u64,
+    /// Transaction field the nonce is written to
+    pub nonce_strategy: NonceStrategy,
 }
 
 impl MinerConfig {
@@ -20,6 +24,7 @@ impl MinerConfig {
             nonce_offset,
             threads: if threads == 0 { num_cpus::get() } else { threads },
             start_nonce: 0,
+            nonce_strategy: NonceStrategy::Epoch,
         }
     }
 
@@ -28,8 +33,18 @@ impl MinerConfig {
         self
     }
 
-    /// Extract base gas budget from template
-    pub fn base_gas_budget(&self) -> u64 {
+    pub fn with_nonce_strategy(mut self, strategy: NonceStrategy) -> Self {
+        self.nonce_strategy = strategy;
+        self
+    }
+
+    /// Number of distinct nonces, None unless the strategy varies a bounded field
+    pub fn nonce_count(&self) -> Option<u64> {
+        self.nonce_strategy.nonce_count(self.base_nonce_value())
+    }
+
+    /// Extract the base nonce field value (gas budget, epoch, ...) from the template
+    pub fn base_nonce_value(&self) -> u64 {
         let mut bytes = [0u8; 8];
         bytes.copy_from_slice(&self.tx_template[self.nonce_offset..self.nonce_offset + 8]);
         u64::from_le_bytes(bytes)
@@ -1,12 +1,16 @@
 //! Mining executors - Backend implementations for mining
 
+use crate::mining::best::{BestSearch, ScoredResult, TopK};
 use crate::mining::config::MinerConfig;
-use crate::mining::mode::{MiningMode, MiningResult};
+use crate::mining::mode::{MiningMode, MiningResult, ObjectMatch};
+use crate::mining::nonce::nonce_value;
 use crate::target::TargetChecker;
 
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Instant;
+use sui_types::base_types::ObjectID;
 
 /// Trait for mining execution backends
 pub trait MinerExecutor {
@@ -30,6 +34,276 @@ impl CpuExecutor {
     }
 }
 
+impl CpuExecutor {
+    /// Score every derived ID instead of matching a target, keeping the `top_k` best
+    ///
+    /// Runs until the search budget is spent or `cancel` is set. `on_improve` is called
+    /// from the worker threads every time the best score so far rises.
+    ///
+    /// Each ID in the mode's `index_range()` is scored on its own; there is no target, so
+    /// modes that need several matches per transaction (or match the digest) can't be
+    /// scored and callers reject them.
+    pub fn mine_best<M, F>(
+        &self,
+        mode: M,
+        config: &MinerConfig,
+        search: &BestSearch,
+        total_attempts: Arc<AtomicU64>,
+        cancel: Arc<AtomicBool>,
+        on_improve: F,
+    ) -> Vec<ScoredResult>
+    where
+        M: MiningMode,
+        F: Fn(&ScoredResult) + Send + Sync + 'static,
+    {
+        let top = Arc::new(Mutex::new(TopK::new(search.top_k)));
+        // Cached `TopK::threshold` so workers only lock for candidates that will be kept
+        let threshold = Arc::new(AtomicU32::new(0));
+        let on_improve = Arc::new(on_improve);
+        let done = Arc::new(AtomicBool::new(false));
+
+        let nonce_counter = Arc::new(AtomicU64::new(config.start_nonce));
+        let initial_start_nonce = config.start_nonce;
+        let chunk_size = 10_000u64;
+        let base_value = config.base_nonce_value();
+        // Bounded for windowed fields (gas budget, gas price)
+        let nonce_count = config.nonce_count();
+        let (index_start, index_end) = mode.index_range();
+        let started = Instant::now();
+
+        let handles: Vec<_> = (0..config.threads)
+            .map(|_| {
+                let tx_template = config.tx_template.clone();
+                let nonce_offset = config.nonce_offset;
+                let scorer = search.scorer.clone();
+                let budget = search.budget;
+                let cancel = cancel.clone();
+                let done = done.clone();
+                let top = top.clone();
+                let threshold = threshold.clone();
+                let on_improve = on_improve.clone();
+                let nonce_counter = nonce_counter.clone();
+                let total_attempts = total_attempts.clone();
+
+                thread::spawn(move || {
+                    let mut tx_bytes = tx_template;
+
+                    while !cancel.load(Ordering::Relaxed) && !done.load(Ordering::Relaxed) {
+                        let start_nonce = nonce_counter.fetch_add(chunk_size, Ordering::Relaxed);
+
+                        // The last chunk is cut short so exactly `max_attempts` are tried
+                        let spent = start_nonce.saturating_sub(initial_start_nonce);
+                        let chunk_len = budget
+                            .max_attempts
+                            .map_or(chunk_size, |max| max.saturating_sub(spent).min(chunk_size));
+                        let out_of_time =
+                            budget.max_time.is_some_and(|max| started.elapsed() >= max);
+                        if chunk_len == 0 || out_of_time {
+                            done.store(true, Ordering::Relaxed);
+                            return;
+                        }
+
+                        for i in 0..chunk_len {
+                            let n = start_nonce + i;
+                            let Some(value) = nonce_value(base_value, n, nonce_count) else {
+                                // Every value in the window has been tried
+                                done.store(true, Ordering::Relaxed);
+                                return;
+                            };
+
+                            tx_bytes[nonce_offset..nonce_offset + 8]
+                                .copy_from_slice(&value.to_le_bytes());
+
+                            let Ok(tx_data) = bcs::from_bytes::<
+                                sui_types::transaction::TransactionData,
+                            >(&tx_bytes) else {
+                                continue;
+                            };
+                            let tx_digest = tx_data.digest();
+
+                            for object_index in index_start..index_end {
+                                let object_id = ObjectID::derive_id(tx_digest, object_index as u64);
+                                let score = scorer.score(&object_id.into_bytes());
+                                if score < threshold.load(Ordering::Relaxed) {
+                                    continue;
+                                }
+
+                                let scored = ScoredResult {
+                                    score,
+                                    result: MiningResult {
+                                        object_id,
+                                        object_index,
+                                        pattern_index: 0,
+                                        tx_digest,
+                                        matches: vec![ObjectMatch {
+                                            object_id,
+                                            object_index,
+                                            pattern_index: 0,
+                                        }],
+                                        tx_bytes: tx_bytes.clone(),
+                                        nonce: n,
+                                        nonce_value: value,
+                                        attempts: n.saturating_sub(initial_start_nonce),
+                                    },
+                                };
+
+                                let improved = {
+                                    let mut top = top.lock().unwrap();
+                                    let previous_best = top.best_score();
+                                    let kept = top.insert(score, scored.clone());
+                                    threshold.store(top.threshold(), Ordering::Relaxed);
+                                    kept && previous_best.is_none_or(|best| score > best)
+                                };
+                                if improved {
+                                    on_improve(&scored);
+                                }
+                            }
+                        }
+
+                        total_attempts.fetch_add(chunk_len, Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let _ = handle.join();
+        }
+
+        let top = top.lock().unwrap().clone();
+        top.into_vec()
+            .into_iter()
+            .map(|(_, scored)| scored)
+            .collect()
+    }
+
+    /// Keep mining after a match and report every one through `on_match`
+    ///
+    /// Each nonce is tried once, so every (nonce, object index) is reported at most once.
+    /// Modes that need several matches per transaction report them together, others
+    /// report each matching object on its own. Runs until `max_count` matches were
+    /// reported or `cancel` is set and returns the number reported.
+    pub fn mine_all<M, F>(
+        &self,
+        mode: M,
+        config: &MinerConfig,
+        target: &TargetChecker,
+        max_count: Option<u64>,
+        total_attempts: Arc<AtomicU64>,
+        cancel: Arc<AtomicBool>,
+        on_match: F,
+    ) -> u64
+    where
+        M: MiningMode,
+        F: Fn(&MiningResult) + Send + Sync + 'static,
+    {
+        let reported = Arc::new(AtomicU64::new(0));
+        let on_match = Arc::new(on_match);
+        let done = Arc::new(AtomicBool::new(max_count == Some(0)));
+        let combined = mode.min_matches() > 1;
+
+        let nonce_counter = Arc::new(AtomicU64::new(config.start_nonce));
+        let initial_start_nonce = config.start_nonce;
+        let chunk_size = 10_000u64;
+        let base_value = config.base_nonce_value();
+        // Bounded for windowed fields (gas budget, gas price)
+        let nonce_count = config.nonce_count();
+
+        let handles: Vec<_> = (0..config.threads)
+            .map(|_| {
+                let tx_template = config.tx_template.clone();
+                let nonce_offset = config.nonce_offset;
+                let target = target.clone();
+                let mode = mode.clone();
+                let cancel = cancel.clone();
+                let done = done.clone();
+                let reported = reported.clone();
+                let on_match = on_match.clone();
+                let nonce_counter = nonce_counter.clone();
+                let total_attempts = total_attempts.clone();
+
+                thread::spawn(move || {
+                    let mut tx_bytes = tx_template;
+
+                    while !cancel.load(Ordering::Relaxed) && !done.load(Ordering::Relaxed) {
+                        let start_nonce = nonce_counter.fetch_add(chunk_size, Ordering::Relaxed);
+
+                        for i in 0..chunk_size {
+                            let n = start_nonce + i;
+                            let Some(value) = nonce_value(base_value, n, nonce_count) else {
+                                // Every value in the window has been tried
+                                done.store(true, Ordering::Relaxed);
+                                return;
+                            };
+
+                            tx_bytes[nonce_offset..nonce_offset + 8]
+                                .copy_from_slice(&value.to_le_bytes());
+
+                            let Ok(tx_data) = bcs::from_bytes::<
+                                sui_types::transaction::TransactionData,
+                            >(&tx_bytes) else {
+                                continue;
+                            };
+                            let tx_digest = tx_data.digest();
+
+                            let Some(matches) = mode.all_matches(&tx_digest, &target) else {
+                                continue;
+                            };
+                            let groups = if combined {
+                                vec![matches]
+                            } else {
+                                matches.into_iter().map(|m| vec![m]).collect()
+                            };
+
+                            for matches in groups {
+                                // Claim a slot first so the count limit is exact without
+                                // holding a lock while `on_match` runs
+                                let claimed = reported.fetch_update(
+                                    Ordering::SeqCst,
+                                    Ordering::SeqCst,
+                                    |count| {
+                                        let open = max_count.is_none_or(|max| count < max);
+                                        (open && !done.load(Ordering::Relaxed)).then_some(count + 1)
+                                    },
+                                );
+                                let Ok(count) = claimed else {
+                                    done.store(true, Ordering::Relaxed);
+                                    return;
+                                };
+
+                                let matched = matches[0].clone();
+                                on_match(&MiningResult {
+                                    object_id: matched.object_id,
+                                    object_index: matched.object_index,
+                                    pattern_index: matched.pattern_index,
+                                    tx_digest,
+                                    matches,
+                                    tx_bytes: tx_bytes.clone(),
+                                    nonce: n,
+                                    nonce_value: value,
+                                    attempts: n.saturating_sub(initial_start_nonce),
+                                });
+                                if max_count.is_some_and(|max| count + 1 >= max) {
+                                    done.store(true, Ordering::Relaxed);
+                                    return;
+                                }
+                            }
+                        }
+
+                        total_attempts.fetch_add(chunk_size, Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let _ = handle.join();
+        }
+
+        reported.load(Ordering::SeqCst)
+    }
+}
+
 impl Default for CpuExecutor {
     fn default() -> Self {
         Self::new()
@@ -52,7 +326,9 @@ impl MinerExecutor for CpuExecutor {
         let nonce_counter = Arc::new(AtomicU64::new(config.start_nonce));
         let initial_start_nonce = config.start_nonce;
         let chunk_size = 10_000u64;
-        let base_gas_budget = config.base_gas_budget();
+        let base_value = config.base_nonce_value();
+        // Bounded for windowed fields (gas budget, gas price)
+        let nonce_count = config.nonce_count();
 
         let handles: Vec<_> = (0..config.threads)
             .map(|_| {
@@ -80,11 +356,14 @@ impl MinerExecutor for CpuExecutor {
                             }
 
                             let n = start_nonce + i;
-                            let varied_gas_budget = base_gas_budget.wrapping_add(n);
+                            let Some(value) = nonce_value(base_value, n, nonce_count) else {
+                                // Every value in the window has been tried
+                                return;
+                            };
 
                             // Modify nonce in buffer
                             tx_bytes[nonce_offset..nonce_offset + 8]
-                                .copy_from_slice(&varied_gas_budget.to_le_bytes());
+                                .copy_from_slice(&value.to_le_bytes());
 
                             // Parse transaction
                             if let Ok(tx_data) = bcs::from_bytes::<
@@ -94,8 +373,7 @@ impl MinerExecutor for CpuExecutor {
                                 let tx_digest = tx_data.digest();
 
                                 // Use mode to check for match
-                                if let Some((object_id, object_index)) =
-                                    mode.check_match(&tx_digest, &target)
+                                if let Some(matches) = mode.check_matches(&tx_digest, &target)
                                 {
                                     // Found!
                                     if found
@@ -109,13 +387,16 @@ impl MinerExecutor for CpuExecutor {
                                     {
                                         let relative_attempts =
                                             n.saturating_sub(initial_start_nonce);
+                                        let matched = matches[0].clone();
                                         let result = MiningResult {
-                                            object_id,
-                                            object_index,
+                                            object_id: matched.object_id,
+                                            object_index: matched.object_index,
+                                            pattern_index: matched.pattern_index,
                                             tx_digest,
+                                            matches,
                                             tx_bytes: tx_bytes.clone(),
                                             nonce: n,
-                                            gas_budget_used: varied_gas_budget,
+                                            nonce_value: value,
                                             attempts: relative_attempts,
                                         };
                                         *result_holder.lock().unwrap() = Some(result);
@@ -159,4 +440,111 @@ mod tests {
         let executor = CpuExecutor::default();
         let _ = executor;
     }
+
+    /// A 4-coin split transaction with the epoch nonce
+    fn split_template() -> (Vec<u8>, usize, u16) {
+        use crate::common::create_split_tx_template;
+        use crate::mining::nonce::NonceStrategy;
+        use sui_types::base_types::{ObjectDigest, SequenceNumber, SuiAddress};
+
+        let gas_payment = (
+            ObjectID::ZERO,
+            SequenceNumber::from_u64(1),
+            ObjectDigest::new([0; 32]),
+        );
+        create_split_tx_template(
+            SuiAddress::ZERO,
+            vec![1, 2, 3, 4],
+            1_000_000,
+            1_000,
+            gas_payment,
+            NonceStrategy::Epoch,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mine_all_reports_every_coin_up_to_count() {
+        use crate::mining::mode::GasCoinMode;
+
+        let (tx_template, nonce_offset, num_outputs) = split_template();
+        // One thread so the nonces are visited in order
+        let config = MinerConfig::new(tx_template, nonce_offset, 1).with_start_nonce(7);
+        // Every ID starts with one of the 16 nibbles, so all four coins match each time
+        let nibbles: Vec<String> = (0..16).map(|n| format!("{:x}", n)).collect();
+        let target = TargetChecker::from_prefix_set(&nibbles).unwrap();
+
+        let found = Arc::new(Mutex::new(Vec::new()));
+        let sink = found.clone();
+        let reported = CpuExecutor::new().mine_all(
+            GasCoinMode::new(num_outputs),
+            &config,
+            &target,
+            Some(6),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicBool::new(false)),
+            move |result| {
+                sink.lock()
+                    .unwrap()
+                    .push((result.nonce, result.object_index));
+            },
+        );
+
+        assert_eq!(reported, 6);
+        let found = found.lock().unwrap().clone();
+        assert_eq!(found, vec![(7, 0), (7, 1), (7, 2), (7, 3), (8, 0), (8, 1)]);
+    }
+
+    #[test]
+    fn test_mine_best_keeps_top_k_within_budget() {
+        use crate::mining::best::SearchBudget;
+        use crate::mining::mode::GasCoinMode;
+        use crate::score::Scorer;
+
+        let (tx_template, nonce_offset, num_outputs) = split_template();
+        let config = MinerConfig::new(tx_template.clone(), nonce_offset, 1).with_start_nonce(7);
+        // Not a multiple of the 10k chunk, so the last chunk has to be cut short
+        let search = BestSearch::new(Scorer::LeadingZeros, 3).with_budget(SearchBudget {
+            max_time: None,
+            max_attempts: Some(25_000),
+        });
+
+        let total_attempts = Arc::new(AtomicU64::new(0));
+        let results = CpuExecutor::new().mine_best(
+            GasCoinMode::new(num_outputs),
+            &config,
+            &search,
+            total_attempts.clone(),
+            Arc::new(AtomicBool::new(false)),
+            |_| {},
+        );
+        assert_eq!(total_attempts.load(Ordering::Relaxed), 25_000);
+
+        // Score every ID of the same nonces directly
+        let base_value = config.base_nonce_value();
+        let mut tx_bytes = tx_template;
+        let mut scores = Vec::new();
+        for n in 7..7 + 25_000 {
+            tx_bytes[nonce_offset..nonce_offset + 8]
+                .copy_from_slice(&base_value.wrapping_add(n).to_le_bytes());
+            let tx_data: sui_types::transaction::TransactionData =
+                bcs::from_bytes(&tx_bytes).unwrap();
+            let tx_digest = tx_data.digest();
+            for index in 0..num_outputs {
+                let object_id = ObjectID::derive_id(tx_digest, index as u64);
+                scores.push(search.scorer.score(&object_id.into_bytes()));
+            }
+        }
+        scores.sort_unstable_by(|a, b| b.cmp(a));
+
+        let found: Vec<u32> = results.iter().map(|r| r.score).collect();
+        assert_eq!(found, scores[..3]);
+        for scored in &results {
+            assert!((7..7 + 25_000).contains(&scored.result.nonce));
+            assert_eq!(
+                search.scorer.score(&scored.result.object_id.into_bytes()),
+                scored.score
+            );
+        }
+    }
 }
@@ -3,7 +3,7 @@ use crate::mining::MinerConfig;
 #[cfg(feature = "gpu")]
 use crate::mining::mode::MiningMode;
 #[cfg(feature = "gpu")]
-use crate::mining::mode::MiningResult;
+use crate::mining::mode::{MiningResult, ObjectMatch};
 #[cfg(feature = "gpu")]
 use crate::target::TargetChecker;
 #[cfg(feature = "gpu")]
@@ -40,6 +40,21 @@ impl GpuExecutor {
         total_attempts: Arc<AtomicU64>,
         cancel: Arc<AtomicBool>,
     ) -> Result<Option<MiningResult>> {
+        if !target.is_prefix_only() {
+            anyhow::bail!("GPU mining only supports prefix targets, use CPU mining instead");
+        }
+        if mode.min_matches() > 1 {
+            anyhow::bail!(
+                "GPU mining stops at the first match, use CPU mining for k-of-N matches"
+            );
+        }
+        if config.nonce_count().is_some() {
+            anyhow::bail!(
+                "GPU mining can't keep the nonce inside the {} window, use CPU mining",
+                config.nonce_strategy.label()
+            );
+        }
+
         println!("   Initializing GPU...");
 
         let kernel_src = include_str!("kernel.cl");
@@ -164,7 +179,7 @@ impl GpuExecutor {
             global_work_size
         );
 
-        let base_budget = config.base_gas_budget();
+        let base_budget = config.base_nonce_value();
         let mut current_nonce = config.start_nonce;
 
         // Enforce a minimum gas budget to prevent "InsufficientGas" errors on submission.
@@ -250,10 +265,16 @@ impl GpuExecutor {
                         return Ok(Some(MiningResult {
                             object_id,
                             object_index: matching_index,
+                            pattern_index: 0,
                             tx_digest,
+                            matches: vec![ObjectMatch {
+                                object_id,
+                                object_index: matching_index,
+                                pattern_index: 0,
+                            }],
                             tx_bytes,
                             nonce: nonce.wrapping_sub(base_budget),
-                            gas_budget_used: nonce,
+                            nonce_value: nonce,
                             attempts: nonce
                                 .wrapping_sub(base_budget)
                                 .saturating_sub(config.start_nonce),
@@ -279,10 +300,16 @@ impl GpuExecutor {
                             return Ok(Some(MiningResult {
                                 object_id: raw_object_id,
                                 object_index: matching_index,
+                                pattern_index: 0,
                                 tx_digest: direct_digest,
+                                matches: vec![ObjectMatch {
+                                    object_id: raw_object_id,
+                                    object_index: matching_index,
+                                    pattern_index: 0,
+                                }],
                                 tx_bytes,
                                 nonce: nonce.wrapping_sub(base_budget),
-                                gas_budget_used: nonce,
+                                nonce_value: nonce,
                                 attempts: nonce
                                     .wrapping_sub(base_budget)
                                     .saturating_sub(config.start_nonce),
@@ -326,10 +353,16 @@ impl GpuExecutor {
                             return Ok(Some(MiningResult {
                                 object_id,
                                 object_index: matching_index,
+                                pattern_index: 0,
                                 tx_digest: direct_digest,
+                                matches: vec![ObjectMatch {
+                                    object_id,
+                                    object_index: matching_index,
+                                    pattern_index: 0,
+                                }],
                                 tx_bytes,
                                 nonce: nonce.wrapping_sub(base_budget),
-                                gas_budget_used: nonce,
+                                nonce_value: nonce,
                                 attempts: nonce
                                     .wrapping_sub(base_budget)
                                     .saturating_sub(config.start_nonce),
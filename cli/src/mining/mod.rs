@@ -3,14 +3,26 @@
 //! This module provides trait-based abstractions to separate:
 //! - Mining modes (Package ID vs Gas Coin ID) via `MiningMode` trait
 //! - Execution backends (CPU, future GPU) via `MinerExecutor` trait
+//! - Score-based "best-so-far" searches via `BestSearch`
+//! - The transaction field that carries the nonce via `NonceStrategy`
+//! - Candidate searches that don't vary a transaction (e.g. keypairs) via `SearchSpace`
 
+pub mod best;
 pub mod config;
 pub mod executor;
 pub mod mode;
+pub mod nonce;
+pub mod search;
 
+pub use best::{BestSearch, ScoredResult, SearchBudget};
 pub use config::MinerConfig;
 pub use executor::{CpuExecutor, MinerExecutor};
-pub use mode::{GasCoinMode, PackageMode, SingleObjectMode};
+pub use mode::{
+    CreatedObjectsMode, DigestMode, GasCoinMode, IndexTarget, ObjectRangeMode, PackageMode,
+    SingleObjectMode, creation_index,
+};
+pub use nonce::{NONCE_PLACEHOLDER, NonceStrategy};
+pub use search::{SearchResult, SearchSpace, search};
 
 #[cfg(feature = "gpu")]
 pub mod gpu;
@@ -1,19 +1,53 @@
 //! Mining modes - Define how to check for matching Object IDs
 
+use crate::estimate::Estimate;
 use crate::target::TargetChecker;
 use sui_types::base_types::ObjectID;
 use sui_types::digests::TransactionDigest;
 
+/// A derived Object ID that satisfied the target
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectMatch {
+    pub object_id: ObjectID,
+    pub object_index: u16,
+    /// Index of the matched pattern within the target (0 for single-pattern targets)
+    pub pattern_index: usize,
+}
+
+impl ObjectMatch {
+    /// Derive the Object ID at `object_index` and check it against the target
+    #[inline(always)]
+    pub fn derive(
+        tx_digest: &TransactionDigest,
+        object_index: u16,
+        target: &TargetChecker,
+    ) -> Option<Self> {
+        let object_id = ObjectID::derive_id(*tx_digest, object_index as u64);
+        target
+            .find_match(&object_id.into_bytes())
+            .map(|pattern_index| Self {
+                object_id,
+                object_index,
+                pattern_index,
+            })
+    }
+}
+
 /// Result of a successful mining operation
 #[derive(Clone, Debug)]
 pub struct MiningResult {
     pub object_id: ObjectID,
     pub object_index: u16,
+    /// Index of the matched pattern within the target
+    pub pattern_index: usize,
     pub tx_digest: TransactionDigest,
+    /// Every matching object, starting with the one above (several in k-of-N modes)
+    pub matches: Vec<ObjectMatch>,
     pub tx_bytes: Vec<u8>,
     #[allow(dead_code)]
     pub nonce: u64,
-    pub gas_budget_used: u64,
+    /// Value written into the nonce field (epoch, gas budget, ... per `NonceStrategy`)
+    pub nonce_value: u64,
     pub attempts: u64,
 }
 
@@ -21,12 +55,53 @@ pub struct MiningResult {
 /// Each mode defines how to check if a transaction digest produces a matching ID
 pub trait MiningMode: Send + Sync + Clone + 'static {
     /// Check if the given transaction digest produces a matching Object ID
-    /// Returns Some((object_id, object_index)) if match found, None otherwise
+    /// Returns the matching object (with the matched pattern) if found, None otherwise
     fn check_match(
         &self,
         tx_digest: &TransactionDigest,
         target: &TargetChecker,
-    ) -> Option<(ObjectID, u16)>;
+    ) -> Option<ObjectMatch>;
+
+    /// Check a digest and return every matching object, None unless at least
+    /// `min_matches` of them match
+    fn check_matches(
+        &self,
+        tx_digest: &TransactionDigest,
+        target: &TargetChecker,
+    ) -> Option<Vec<ObjectMatch>> {
+        self.check_match(tx_digest, target).map(|m| vec![m])
+    }
+
+    /// Every matching object in `index_range()`, None unless at least `min_matches` match
+    ///
+    /// Unlike `check_matches`, which may stop at the first match, this is what streaming
+    /// reports.
+    fn all_matches(
+        &self,
+        tx_digest: &TransactionDigest,
+        target: &TargetChecker,
+    ) -> Option<Vec<ObjectMatch>> {
+        let (start, end) = self.index_range();
+        let matches: Vec<ObjectMatch> = (start..end)
+            .filter_map(|index| ObjectMatch::derive(tx_digest, index, target))
+            .collect();
+        (!matches.is_empty() && matches.len() >= self.min_matches() as usize).then_some(matches)
+    }
+
+    /// Number of objects that must match for a digest to count
+    fn min_matches(&self) -> u16 {
+        1
+    }
+
+    /// Per-attempt success estimate, None if the target's probability is unknown
+    fn estimate(&self, target: &TargetChecker) -> Option<Estimate> {
+        Estimate::for_target_at_least(target, self.ids_per_attempt(), self.min_matches() as u32)
+    }
+
+    /// Whether the target applies to the transaction digest instead of derived Object IDs
+    fn matches_digest(&self) -> bool {
+        false
+    }
 
     /// Description for logging
     #[allow(dead_code)]
@@ -37,6 +112,12 @@ pub trait MiningMode: Send + Sync + Clone + 'static {
     fn index_range(&self) -> (u16, u16) {
         (0, 1) // Default for PackageMode
     }
+
+    /// Number of Object IDs checked per attempt (each is an independent chance to match)
+    fn ids_per_attempt(&self) -> u32 {
+        let (start, end) = self.index_range();
+        end.saturating_sub(start) as u32
+    }
 }
 
 /// Package ID mining mode
@@ -49,13 +130,8 @@ impl MiningMode for PackageMode {
         &self,
         tx_digest: &TransactionDigest,
         target: &TargetChecker,
-    ) -> Option<(ObjectID, u16)> {
-        let package_id = ObjectID::derive_id(*tx_digest, 0);
-        if target.matches(&package_id.into_bytes()) {
-            Some((package_id, 0))
-        } else {
-            None
-        }
+    ) -> Option<ObjectMatch> {
+        ObjectMatch::derive(tx_digest, 0, target)
     }
 
     fn description(&self) -> &'static str {
@@ -67,17 +143,119 @@ impl MiningMode for PackageMode {
     }
 }
 
+/// Target for one extra object created by a publish
+#[derive(Clone, Debug)]
+pub struct IndexTarget {
+    /// Created-object index (0 = package, 1.. = objects created by `init`, then the UpgradeCap)
+    pub index: u16,
+    pub target: TargetChecker,
+}
+
+/// Publish mining over the objects it creates
+/// The main target applies at `primary_index` and every extra target must match too
+#[derive(Clone, Debug)]
+pub struct CreatedObjectsMode {
+    pub primary_index: u16,
+    pub extra: Vec<IndexTarget>,
+}
+
+impl CreatedObjectsMode {
+    pub fn new(primary_index: u16, extra: Vec<IndexTarget>) -> Self {
+        Self {
+            primary_index,
+            extra,
+        }
+    }
+}
+
+impl MiningMode for CreatedObjectsMode {
+    fn check_match(
+        &self,
+        tx_digest: &TransactionDigest,
+        target: &TargetChecker,
+    ) -> Option<ObjectMatch> {
+        if !self.extra.is_empty() {
+            return self
+                .check_matches(tx_digest, target)
+                .and_then(|matches| matches.into_iter().next());
+        }
+        ObjectMatch::derive(tx_digest, self.primary_index, target)
+    }
+
+    fn check_matches(
+        &self,
+        tx_digest: &TransactionDigest,
+        target: &TargetChecker,
+    ) -> Option<Vec<ObjectMatch>> {
+        let mut matches = vec![ObjectMatch::derive(tx_digest, self.primary_index, target)?];
+        for extra in &self.extra {
+            matches.push(ObjectMatch::derive(tx_digest, extra.index, &extra.target)?);
+        }
+        Some(matches)
+    }
+
+    fn all_matches(
+        &self,
+        tx_digest: &TransactionDigest,
+        target: &TargetChecker,
+    ) -> Option<Vec<ObjectMatch>> {
+        // Every target applies to its own index, so all of them are always reported
+        self.check_matches(tx_digest, target)
+    }
+
+    fn min_matches(&self) -> u16 {
+        1 + self.extra.len() as u16
+    }
+
+    fn estimate(&self, target: &TargetChecker) -> Option<Estimate> {
+        // Independent IDs against different targets
+        let mut p = target.probability()?;
+        for extra in &self.extra {
+            p *= extra.target.probability()?;
+        }
+        Some(Estimate::new(p, 1))
+    }
+
+    fn description(&self) -> &'static str {
+        "Created Object ID"
+    }
+
+    fn index_range(&self) -> (u16, u16) {
+        (self.primary_index, self.primary_index + 1)
+    }
+}
+
+/// Creation index of `object_id` in a transaction, searching indices below `max_index`
+pub fn creation_index(
+    tx_digest: &TransactionDigest,
+    object_id: &ObjectID,
+    max_index: u16,
+) -> Option<u16> {
+    (0..max_index).find(|&i| ObjectID::derive_id(*tx_digest, i as u64) == *object_id)
+}
+
 /// Gas Coin mining mode
 /// Checks multiple object indices (one per split coin)
 #[derive(Clone, Debug)]
 pub struct GasCoinMode {
     /// Number of coins being created
     pub num_outputs: u16,
+    /// Number of coins that must match (k of N)
+    pub min_matches: u16,
 }
 
 impl GasCoinMode {
     pub fn new(num_outputs: u16) -> Self {
-        Self { num_outputs }
+        Self {
+            num_outputs,
+            min_matches: 1,
+        }
+    }
+
+    /// Only accept digests where at least `min_matches` coins match (clamped to 1..=N)
+    pub fn with_min_matches(mut self, min_matches: u16) -> Self {
+        self.min_matches = min_matches.clamp(1, self.num_outputs.max(1));
+        self
     }
 }
 
@@ -86,15 +264,41 @@ impl MiningMode for GasCoinMode {
         &self,
         tx_digest: &TransactionDigest,
         target: &TargetChecker,
-    ) -> Option<(ObjectID, u16)> {
+    ) -> Option<ObjectMatch> {
+        if self.min_matches > 1 {
+            return self
+                .check_matches(tx_digest, target)
+                .and_then(|matches| matches.into_iter().next());
+        }
         // Check all output indices
+        (0..self.num_outputs).find_map(|index| ObjectMatch::derive(tx_digest, index, target))
+    }
+
+    fn check_matches(
+        &self,
+        tx_digest: &TransactionDigest,
+        target: &TargetChecker,
+    ) -> Option<Vec<ObjectMatch>> {
+        if self.min_matches <= 1 {
+            return self.check_match(tx_digest, target).map(|m| vec![m]);
+        }
+
+        let mut matches = Vec::new();
         for index in 0..self.num_outputs {
-            let object_id = ObjectID::derive_id(*tx_digest, index as u64);
-            if target.matches(&object_id.into_bytes()) {
-                return Some((object_id, index));
+            // Give up as soon as the remaining coins can't reach k
+            let remaining = (self.num_outputs - index) as usize;
+            if matches.len() + remaining < self.min_matches as usize {
+                return None;
+            }
+            if let Some(m) = ObjectMatch::derive(tx_digest, index, target) {
+                matches.push(m);
             }
         }
-        None
+        (matches.len() >= self.min_matches as usize).then_some(matches)
+    }
+
+    fn min_matches(&self) -> u16 {
+        self.min_matches
     }
 
     fn description(&self) -> &'static str {
@@ -124,13 +328,8 @@ impl MiningMode for SingleObjectMode {
         &self,
         tx_digest: &TransactionDigest,
         target: &TargetChecker,
-    ) -> Option<(ObjectID, u16)> {
-        let object_id = ObjectID::derive_id(*tx_digest, self.object_index as u64);
-        if target.matches(&object_id.into_bytes()) {
-            Some((object_id, self.object_index))
-        } else {
-            None
-        }
+    ) -> Option<ObjectMatch> {
+        ObjectMatch::derive(tx_digest, self.object_index, target)
     }
 
     fn description(&self) -> &'static str {
@@ -142,6 +341,76 @@ impl MiningMode for SingleObjectMode {
     }
 }
 
+/// Object range mining mode
+/// Checks every index in `start..end` (e.g. all objects a PTB creates) and reports the first
+/// one that matches
+#[derive(Clone, Debug)]
+pub struct ObjectRangeMode {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl ObjectRangeMode {
+    pub fn new(start: u16, end: u16) -> Self {
+        Self { start, end }
+    }
+}
+
+impl MiningMode for ObjectRangeMode {
+    fn check_match(
+        &self,
+        tx_digest: &TransactionDigest,
+        target: &TargetChecker,
+    ) -> Option<ObjectMatch> {
+        (self.start..self.end).find_map(|index| ObjectMatch::derive(tx_digest, index, target))
+    }
+
+    fn description(&self) -> &'static str {
+        "Object ID (index range)"
+    }
+
+    fn index_range(&self) -> (u16, u16) {
+        (self.start, self.end)
+    }
+}
+
+/// Transaction digest mining mode
+/// Matches the digest itself (e.g. a Base58 target); the digest bytes are reported as the
+/// matched `object_id` and nothing is derived
+#[derive(Clone, Debug)]
+pub struct DigestMode;
+
+impl MiningMode for DigestMode {
+    fn check_match(
+        &self,
+        tx_digest: &TransactionDigest,
+        target: &TargetChecker,
+    ) -> Option<ObjectMatch> {
+        let digest = tx_digest.into_inner();
+        target.find_match(&digest).map(|pattern_index| ObjectMatch {
+            object_id: ObjectID::new(digest),
+            object_index: 0,
+            pattern_index,
+        })
+    }
+
+    fn all_matches(
+        &self,
+        tx_digest: &TransactionDigest,
+        target: &TargetChecker,
+    ) -> Option<Vec<ObjectMatch>> {
+        self.check_match(tx_digest, target).map(|m| vec![m])
+    }
+
+    fn matches_digest(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &'static str {
+        "Transaction Digest"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,6 +428,14 @@ mod tests {
         assert_eq!(mode.num_outputs, 3);
     }
 
+    #[test]
+    fn test_ids_per_attempt() {
+        assert_eq!(PackageMode.ids_per_attempt(), 1);
+        assert_eq!(GasCoinMode::new(4).ids_per_attempt(), 4);
+        assert_eq!(SingleObjectMode::new(3).ids_per_attempt(), 1);
+        assert_eq!(ObjectRangeMode::new(2, 6).ids_per_attempt(), 4);
+    }
+
     #[test]
     fn test_package_mode_derives_index_0() {
         // Create a simple target that matches anything starting with 0x00
@@ -172,9 +449,9 @@ mod tests {
         // Package ID should be derived with index 0
         let expected_id = ObjectID::derive_id(tx_digest, 0);
 
-        if let Some((found_id, index)) = mode.check_match(&tx_digest, &target) {
-            assert_eq!(found_id, expected_id);
-            assert_eq!(index, 0);
+        if let Some(found) = mode.check_match(&tx_digest, &target) {
+            assert_eq!(found.object_id, expected_id);
+            assert_eq!(found.object_index, 0);
         }
         // Note: Test may not match if derive_id doesn't produce 0x00... prefix
     }
@@ -189,9 +466,150 @@ mod tests {
 
         // Should check indices 0, 1, 2, 3, 4
         let result = mode.check_match(&tx_digest, &target);
-        if let Some((_id, index)) = result {
+        if let Some(found) = result {
             // If found, index should be in range 0..5
-            assert!(index < 5);
+            assert!(found.object_index < 5);
         }
     }
+
+    #[test]
+    fn test_gas_coin_mode_k_of_n() {
+        let tx_digest = TransactionDigest::new([9u8; 32]);
+        let ids: Vec<String> = (0..4)
+            .map(|i| hex::encode(ObjectID::derive_id(tx_digest, i).as_ref()))
+            .collect();
+
+        // Coins 1 and 3 match
+        let target = TargetChecker::from_prefix_set([&ids[1][..6], &ids[3][..6]]).unwrap();
+        let mode = GasCoinMode::new(4).with_min_matches(2);
+        assert_eq!(mode.min_matches(), 2);
+
+        let matches = mode.check_matches(&tx_digest, &target).unwrap();
+        let indices: Vec<u16> = matches.iter().map(|m| m.object_index).collect();
+        assert_eq!(indices, vec![1, 3]);
+        assert_eq!(
+            mode.check_match(&tx_digest, &target).unwrap().object_index,
+            1
+        );
+
+        let mode = mode.with_min_matches(3);
+        assert!(mode.check_matches(&tx_digest, &target).is_none());
+        assert!(mode.check_match(&tx_digest, &target).is_none());
+        assert_eq!(GasCoinMode::new(4).with_min_matches(9).min_matches, 4);
+    }
+
+    #[test]
+    fn test_all_matches_reports_every_index() {
+        let tx_digest = TransactionDigest::new([9u8; 32]);
+        let ids: Vec<String> = (0..4)
+            .map(|i| hex::encode(ObjectID::derive_id(tx_digest, i).as_ref()))
+            .collect();
+        let target = TargetChecker::from_prefix_set([&ids[1][..6], &ids[3][..6]]).unwrap();
+        let indices = |matches: Option<Vec<ObjectMatch>>| -> Vec<u16> {
+            matches.unwrap().iter().map(|m| m.object_index).collect()
+        };
+
+        // check_matches stops at coin 1 unless k > 1, all_matches never does
+        let mode = GasCoinMode::new(4);
+        assert_eq!(indices(mode.check_matches(&tx_digest, &target)), vec![1]);
+        assert_eq!(indices(mode.all_matches(&tx_digest, &target)), vec![1, 3]);
+        assert_eq!(
+            indices(ObjectRangeMode::new(0, 4).all_matches(&tx_digest, &target)),
+            vec![1, 3]
+        );
+        assert!(
+            GasCoinMode::new(4)
+                .with_min_matches(3)
+                .all_matches(&tx_digest, &target)
+                .is_none()
+        );
+        assert!(PackageMode.all_matches(&tx_digest, &target).is_none());
+    }
+
+    #[test]
+    fn test_created_objects_mode_requires_all() {
+        let tx_digest = TransactionDigest::new([5u8; 32]);
+        let id = |i: u64| hex::encode(ObjectID::derive_id(tx_digest, i).as_ref());
+        let extra = |index: u16, i: u64| IndexTarget {
+            index,
+            target: TargetChecker::from_hex_prefix(&id(i)[..4]).unwrap(),
+        };
+
+        // Config object at index 1, UpgradeCap at index 2
+        let target = TargetChecker::from_hex_prefix(&id(1)[..4]).unwrap();
+        let mode = CreatedObjectsMode::new(1, vec![extra(2, 2)]);
+        assert_eq!(mode.min_matches(), 2);
+        let matches = mode.check_matches(&tx_digest, &target).unwrap();
+        let indices: Vec<u16> = matches.iter().map(|m| m.object_index).collect();
+        assert_eq!(indices, vec![1, 2]);
+
+        // Same targets, but the cap is expected at index 3
+        let mode = CreatedObjectsMode::new(1, vec![extra(3, 2)]);
+        assert!(mode.check_matches(&tx_digest, &target).is_none());
+        assert!(mode.check_match(&tx_digest, &target).is_none());
+
+        let estimate = mode.estimate(&target).unwrap();
+        assert!((estimate.probability() - 16f64.powi(-8)).abs() < 1e-20);
+
+        // Without extras only the primary index is derived
+        let mode = CreatedObjectsMode::new(1, Vec::new());
+        assert_eq!(mode.index_range(), (1, 2));
+        assert_eq!(
+            mode.check_match(&tx_digest, &target).unwrap().object_index,
+            1
+        );
+    }
+
+    #[test]
+    fn test_object_range_mode_reports_index() {
+        let tx_digest = TransactionDigest::new([4u8; 32]);
+        let id = hex::encode(ObjectID::derive_id(tx_digest, 3).as_ref());
+        let target = TargetChecker::from_hex_prefix(&id[..6]).unwrap();
+
+        let found = ObjectRangeMode::new(0, 5)
+            .check_match(&tx_digest, &target)
+            .unwrap();
+        assert_eq!(found.object_index, 3);
+        assert!(
+            ObjectRangeMode::new(4, 8)
+                .check_match(&tx_digest, &target)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_digest_mode_matches_base58_digest() {
+        let tx_digest = TransactionDigest::new([11u8; 32]);
+        let encoded = tx_digest.to_string();
+
+        let target = TargetChecker::from_base58_prefix(&encoded[..2], false).unwrap();
+        let found = DigestMode.check_match(&tx_digest, &target).unwrap();
+        assert_eq!(found.object_id.into_bytes(), tx_digest.into_inner());
+
+        let other = TargetChecker::from_base58_prefix("zz", false).unwrap();
+        assert!(DigestMode.check_match(&tx_digest, &other).is_none());
+    }
+
+    #[test]
+    fn test_creation_index() {
+        let tx_digest = TransactionDigest::new([3u8; 32]);
+        let object_id = ObjectID::derive_id(tx_digest, 4);
+        assert_eq!(creation_index(&tx_digest, &object_id, 8), Some(4));
+        assert_eq!(creation_index(&tx_digest, &object_id, 4), None);
+    }
+
+    #[test]
+    fn test_check_match_reports_pattern_index() {
+        let tx_digest = TransactionDigest::new([7u8; 32]);
+        let object_id = ObjectID::derive_id(tx_digest, 0);
+        let id_hex = hex::encode(object_id.as_ref());
+
+        // The real leading nibbles are the second entry of the set
+        let target = TargetChecker::from_prefix_set(["ffff", &id_hex[..4], "0000"]).unwrap();
+        let mode = PackageMode;
+
+        let found = mode.check_match(&tx_digest, &target).unwrap();
+        assert_eq!(found.object_id, object_id);
+        assert_eq!(found.pattern_index, 1);
+    }
 }
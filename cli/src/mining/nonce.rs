@@ -0,0 +1,103 @@
+//! Nonce strategies - which transaction field carries the nonce
+//!
+//! The template holds the field's base value at `nonce_offset` and the executors write
+//! `base + n` there. Unbounded fields take any u64, windowed fields (gas budget, gas price)
+//! only `base..=max`, so their search space is finite and nonce `n` is the offset into the
+//! window: a search starts at 0 and resumes at `start_nonce + attempts`.
+
+use std::fmt;
+
+/// Placeholder written into the nonce field to check its computed offset in the transaction
+pub const NONCE_PLACEHOLDER: u64 = 0xAAAAAAAAAAAAAAAA;
+
+/// Transaction field varied while mining
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonceStrategy {
+    /// `TransactionExpiration::Epoch`, any u64 (the default)
+    #[default]
+    Epoch,
+    /// Gas budget from the template's budget up to `max`
+    GasBudget { max: u64 },
+    /// Gas price from the template's price (normally the reference price) up to `max`
+    GasPrice { max: u64 },
+    /// An unused `pure` u64 input appended to the PTB, any u64; the expiration is left unset
+    PureInput,
+}
+
+impl NonceStrategy {
+    /// Name of the varied field, for result reporting
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Epoch => "Expiration Epoch",
+            Self::GasBudget { .. } => "Gas Budget",
+            Self::GasPrice { .. } => "Gas Price",
+            Self::PureInput => "Nonce Input",
+        }
+    }
+
+    /// Highest value the field may take, None if any u64 works
+    pub fn max(&self) -> Option<u64> {
+        match self {
+            Self::GasBudget { max } | Self::GasPrice { max } => Some(*max),
+            Self::Epoch | Self::PureInput => None,
+        }
+    }
+
+    /// Number of distinct nonces when the field starts at `base`, None if unbounded
+    pub fn nonce_count(&self, base: u64) -> Option<u64> {
+        self.max().map(|max| max.saturating_sub(base).saturating_add(1))
+    }
+}
+
+impl fmt::Display for NonceStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max() {
+            Some(max) => write!(f, "{} (up to {})", self.label(), max),
+            None => f.write_str(self.label()),
+        }
+    }
+}
+
+/// Value written for nonce `n`, None once `n` is past the end of a window
+///
+/// The window doesn't depend on where a search started, so resuming a windowed search at
+/// the nonce it stopped at never repeats a value.
+#[inline(always)]
+pub fn nonce_value(base: u64, n: u64, count: Option<u64>) -> Option<u64> {
+    match count {
+        None => Some(base.wrapping_add(n)),
+        Some(count) => (n < count).then(|| base + n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_covers_every_value_once() {
+        let strategy = NonceStrategy::GasBudget { max: 1_009 };
+        let count = strategy.nonce_count(1_000);
+        assert_eq!(count, Some(10));
+
+        let values: Vec<u64> = (0..20)
+            .map_while(|n| nonce_value(1_000, n, count))
+            .collect();
+        assert_eq!(values, (1_000..=1_009).collect::<Vec<_>>());
+
+        // Resuming at start + attempts continues with the untried values only
+        let resumed: Vec<u64> = (4..20)
+            .map_while(|n| nonce_value(1_000, n, count))
+            .collect();
+        assert_eq!(resumed, (1_004..=1_009).collect::<Vec<_>>());
+        assert_eq!(nonce_value(1_000, 10, count), None);
+    }
+
+    #[test]
+    fn test_unbounded_strategies() {
+        assert_eq!(NonceStrategy::Epoch.nonce_count(5), None);
+        assert_eq!(NonceStrategy::PureInput.nonce_count(5), None);
+        assert_eq!(nonce_value(u64::MAX, 2, None), Some(1));
+        assert_eq!(NonceStrategy::GasPrice { max: 1_500 }.nonce_count(1_000), Some(501));
+    }
+}
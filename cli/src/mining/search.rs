@@ -0,0 +1,154 @@
+//! Generic search executor - For candidates that don't come from a transaction nonce
+//!
+//! Address-style mining (keypairs, salts, ...) generates independent candidates on each
+//! worker thread instead of varying a transaction template, so it plugs in here via the
+//! `SearchSpace` trait rather than `MiningMode`.
+
+use crate::target::TargetChecker;
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A space of candidates searched in parallel until one matches the target
+pub trait SearchSpace: Send + Sync + Clone + 'static {
+    /// Per-thread state (e.g. an RNG or an incrementing counter)
+    type Worker: Send;
+    /// What a matching candidate yields
+    type Found: Send + Clone + 'static;
+
+    /// Create the state for one worker thread
+    fn worker(&self, thread_index: usize) -> Self::Worker;
+
+    /// Generate and check one candidate
+    fn try_candidate(
+        &self,
+        worker: &mut Self::Worker,
+        target: &TargetChecker,
+    ) -> Option<Self::Found>;
+
+    /// Description for logging
+    #[allow(dead_code)]
+    fn description(&self) -> &'static str;
+}
+
+/// A match found by `search`
+#[derive(Clone, Debug)]
+pub struct SearchResult<T> {
+    pub found: T,
+    pub attempts: u64,
+}
+
+/// Run `space` on `threads` threads until a candidate matches or `cancel` is set
+pub fn search<S: SearchSpace>(
+    space: S,
+    threads: usize,
+    target: &TargetChecker,
+    total_attempts: Arc<AtomicU64>,
+    cancel: Arc<AtomicBool>,
+) -> Option<SearchResult<S::Found>> {
+    let found = Arc::new(AtomicBool::new(false));
+    let result_holder: Arc<Mutex<Option<S::Found>>> = Arc::new(Mutex::new(None));
+    let chunk_size = 1_000u64;
+
+    let handles: Vec<_> = (0..threads.max(1))
+        .map(|thread_index| {
+            let space = space.clone();
+            let target = target.clone();
+            let cancel = cancel.clone();
+            let found = found.clone();
+            let result_holder = result_holder.clone();
+            let total_attempts = total_attempts.clone();
+
+            thread::spawn(move || {
+                let mut worker = space.worker(thread_index);
+
+                while !cancel.load(Ordering::Relaxed) && !found.load(Ordering::Relaxed) {
+                    for i in 0..chunk_size {
+                        if let Some(hit) = space.try_candidate(&mut worker, &target) {
+                            total_attempts.fetch_add(i + 1, Ordering::Relaxed);
+                            if found
+                                .compare_exchange(false, true, Ordering::SeqCst, Ordering::Relaxed)
+                                .is_ok()
+                            {
+                                *result_holder.lock().unwrap() = Some(hit);
+                            }
+                            return;
+                        }
+                    }
+
+                    total_attempts.fetch_add(chunk_size, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    let found = result_holder.lock().unwrap().take()?;
+    Some(SearchResult {
+        found,
+        attempts: total_attempts.load(Ordering::Relaxed),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up per thread and "derives" the ID by putting the counter in the first bytes
+    #[derive(Clone)]
+    struct CounterSpace;
+
+    impl SearchSpace for CounterSpace {
+        type Worker = u64;
+        type Found = u64;
+
+        fn worker(&self, thread_index: usize) -> u64 {
+            (thread_index as u64) << 48
+        }
+
+        fn try_candidate(&self, worker: &mut u64, target: &TargetChecker) -> Option<u64> {
+            *worker += 1;
+            let mut id = [0u8; 32];
+            id[..8].copy_from_slice(&worker.to_be_bytes());
+            target.matches(&id).then_some(*worker)
+        }
+
+        fn description(&self) -> &'static str {
+            "Counter"
+        }
+    }
+
+    #[test]
+    fn test_search_finds_candidate() {
+        // Thread 0 counts 1, 2, 3, ... and is the only one that can reach 0x0000..0500
+        let target = TargetChecker::from_hex_prefix("0000000000000500").unwrap();
+        let result = search(
+            CounterSpace,
+            2,
+            &target,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicBool::new(false)),
+        )
+        .unwrap();
+        assert_eq!(result.found, 0x500);
+        assert!(result.attempts >= 0x500);
+    }
+
+    #[test]
+    fn test_search_stops_when_cancelled() {
+        let target = TargetChecker::from_hex_prefix("ffffffff").unwrap();
+        let cancel = Arc::new(AtomicBool::new(true));
+        let result = search(
+            CounterSpace,
+            1,
+            &target,
+            Arc::new(AtomicU64::new(0)),
+            cancel,
+        );
+        assert!(result.is_none());
+    }
+}
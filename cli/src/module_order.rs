@@ -1,15 +1,182 @@
 //! Module ordering for multi-module packages
 //!
-//! Modules are already sorted alphabetically by the loader.
-//! This matches the order that `sui client publish` uses.
+//! `sui client publish` sends a package's modules in dependency order: every module comes
+//! after the package modules it uses. The graph is built from each module's handles that
+//! point at the package's own address, and ties are broken by module name so the order (and
+//! with it the transaction digest) doesn't depend on how the files were listed.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use move_binary_format::CompiledModule;
+use std::collections::{BTreeMap, BTreeSet};
 
-/// Pass through modules without reordering.
-/// Modules should already be sorted alphabetically by filename,
-/// which matches the order used by `sui client publish`.
+/// Sort a package's compiled modules so that dependencies come first
 pub fn sort_modules_by_dependency(modules: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>> {
-    // Already sorted by filename in load_module_bytes()
-    // Just pass through without modification
-    Ok(modules)
+    let compiled = deserialize_modules(&modules)?;
+    let Some(first) = compiled.first() else {
+        return Ok(modules);
+    };
+
+    // Modules of one package share its self address (0x0 before publishing)
+    let address = *first.address();
+    if let Some(other) = compiled.iter().find(|m| *m.address() != address) {
+        anyhow::bail!(
+            "Modules of one package must share an address, but {} is at {} and {} at {}",
+            first.name(),
+            address,
+            other.name(),
+            other.address()
+        );
+    }
+
+    let names: Vec<&str> = compiled.iter().map(|m| m.name().as_str()).collect();
+    let mut index_by_name = BTreeMap::new();
+    for (i, name) in names.iter().enumerate() {
+        if index_by_name.insert(*name, i).is_some() {
+            anyhow::bail!("Module {} appears more than once", name);
+        }
+    }
+
+    // pending[i]: package modules i still waits for; dependents[j]: modules that use j
+    let mut pending = vec![BTreeSet::new(); compiled.len()];
+    let mut dependents = vec![Vec::new(); compiled.len()];
+    for (i, module) in compiled.iter().enumerate() {
+        for dep in module.immediate_dependencies() {
+            if *dep.address() != address {
+                continue;
+            }
+            let j = *index_by_name.get(dep.name().as_str()).with_context(|| {
+                format!(
+                    "Module {} uses {}::{}, which is not part of the package",
+                    names[i],
+                    address,
+                    dep.name()
+                )
+            })?;
+            if pending[i].insert(j) {
+                dependents[j].push(i);
+            }
+        }
+    }
+
+    // Kahn's algorithm, always taking the ready module with the smallest name
+    let mut ready: BTreeSet<(&str, usize)> = (0..compiled.len())
+        .filter(|&i| pending[i].is_empty())
+        .map(|i| (names[i], i))
+        .collect();
+    let mut order = Vec::with_capacity(compiled.len());
+    while let Some((_, i)) = ready.pop_first() {
+        order.push(i);
+        for &dependent in &dependents[i] {
+            pending[dependent].remove(&i);
+            if pending[dependent].is_empty() {
+                ready.insert((names[dependent], dependent));
+            }
+        }
+    }
+
+    if order.len() < compiled.len() {
+        let cyclic: Vec<&str> = (0..compiled.len())
+            .filter(|&i| !pending[i].is_empty())
+            .map(|i| names[i])
+            .collect();
+        anyhow::bail!("Dependency cycle between modules: {}", cyclic.join(", "));
+    }
+
+    let mut modules: Vec<Option<Vec<u8>>> = modules.into_iter().map(Some).collect();
+    Ok(order
+        .into_iter()
+        .map(|i| modules[i].take().expect("each module is ordered once"))
+        .collect())
+}
+
+/// Deserialize every module's bytecode
+pub fn deserialize_modules(modules: &[Vec<u8>]) -> Result<Vec<CompiledModule>> {
+    modules
+        .iter()
+        .enumerate()
+        .map(|(i, bytes)| {
+            CompiledModule::deserialize_with_defaults(bytes)
+                .map_err(|e| anyhow::anyhow!("Module #{} is not valid Move bytecode: {}", i, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::{
+        AddressIdentifierIndex, IdentifierIndex, ModuleHandle, empty_module,
+    };
+    use sui_types::{Identifier, SUI_FRAMEWORK_ADDRESS};
+
+    /// A module without code that uses the given modules of its own package
+    fn module(name: &str, deps: &[&str]) -> Vec<u8> {
+        let mut module = empty_module();
+        module.identifiers[0] = Identifier::new(name).unwrap();
+        for dep in deps {
+            module.identifiers.push(Identifier::new(*dep).unwrap());
+            module.module_handles.push(ModuleHandle {
+                address: AddressIdentifierIndex(0),
+                name: IdentifierIndex((module.identifiers.len() - 1) as u16),
+            });
+        }
+        let mut bytes = Vec::new();
+        module
+            .serialize_with_version(module.version, &mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    fn names(modules: &[Vec<u8>]) -> Vec<String> {
+        modules
+            .iter()
+            .map(|bytes| {
+                let module = CompiledModule::deserialize_with_defaults(bytes).unwrap();
+                module.name().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dependencies_come_first() {
+        let modules = vec![
+            module("a_market", &["c_coin", "b_vault"]),
+            module("b_vault", &["c_coin"]),
+            module("c_coin", &[]),
+            module("d_events", &[]),
+        ];
+        let sorted = sort_modules_by_dependency(modules).unwrap();
+        assert_eq!(
+            names(&sorted),
+            ["c_coin", "b_vault", "a_market", "d_events"]
+        );
+    }
+
+    #[test]
+    fn test_cycle_is_an_error() {
+        let modules = vec![module("a", &["b"]), module("b", &["a"]), module("c", &[])];
+        let err = sort_modules_by_dependency(modules).unwrap_err();
+        assert!(err.to_string().contains("cycle between modules: a, b"));
+    }
+
+    #[test]
+    fn test_modules_must_share_an_address() {
+        let mut other = empty_module();
+        other.identifiers[0] = Identifier::new("b").unwrap();
+        other.address_identifiers[0] = SUI_FRAMEWORK_ADDRESS;
+        let mut bytes = Vec::new();
+        other
+            .serialize_with_version(other.version, &mut bytes)
+            .unwrap();
+
+        let err = sort_modules_by_dependency(vec![module("a", &[]), bytes]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Modules of one package must share an address, but a is at {} and b at {}",
+                empty_module().address_identifiers[0],
+                SUI_FRAMEWORK_ADDRESS
+            )
+        );
+    }
 }
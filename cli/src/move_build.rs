@@ -0,0 +1,467 @@
+//! Reading a Move package's build output
+//!
+//! `sui move build` writes `build/<Pkg>/` with `BuildInfo.yaml`, the package's own modules
+//! in `bytecode_modules/` and its dependencies' bytecode in `bytecode_modules/dependencies/`.
+//! `Move.lock` lists the dependency packages and where their sources are; each dependency
+//! records its published address per environment in its own `Move.lock` (or as
+//! `published-at` in its `Move.toml`), together with the chain ID of that environment.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use sui_types::base_types::ObjectID;
+
+const BUILD_INFO: &str = "BuildInfo.yaml";
+const MANIFEST: &str = "Move.toml";
+const LOCK_FILE: &str = "Move.lock";
+
+/// Selects the `[env.<name>]` entry of each dependency's `Move.lock`
+#[derive(Clone, Debug, Default)]
+pub struct LockEnvironment {
+    /// Entry name (e.g. `testnet`), None to pick the entry recorded for `chain_id`
+    pub name: Option<String>,
+    /// Chain the transaction is for, checked against the entry's `chain-id` when both are known
+    pub chain_id: Option<String>,
+}
+
+impl LockEnvironment {
+    /// The selected entry, None if the lock file has none for this environment
+    fn select(&self, mut envs: BTreeMap<String, LockEnv>) -> Result<Option<LockEnv>> {
+        let selected = match (&self.name, &self.chain_id) {
+            (Some(name), _) => envs.remove_entry(name),
+            (None, Some(chain_id)) => envs
+                .into_iter()
+                .find(|(_, entry)| entry.chain_id.as_ref() == Some(chain_id)),
+            (None, None) => None,
+        };
+        let Some((name, entry)) = selected else {
+            return Ok(None);
+        };
+        if let (Some(chain_id), Some(recorded)) = (&self.chain_id, &entry.chain_id)
+            && chain_id != recorded
+        {
+            anyhow::bail!(
+                "Move.lock environment {} is chain {}, but the RPC is on chain {}",
+                name,
+                recorded,
+                chain_id
+            );
+        }
+        Ok(Some(entry))
+    }
+}
+
+impl fmt::Display for LockEnvironment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.name, &self.chain_id) {
+            (Some(name), _) => f.write_str(name),
+            (None, Some(chain_id)) => write!(f, "chain {}", chain_id),
+            (None, None) => f.write_str("no environment"),
+        }
+    }
+}
+
+/// A dependency package from `Move.lock` and its addresses in the selected environment
+#[derive(Clone, Debug)]
+pub struct PublishedDependency {
+    pub name: String,
+    /// ID the bytecode refers to, when the environment records it
+    pub original_id: Option<ObjectID>,
+    /// ID a publish must link against (the latest version), None if unknown
+    pub published_at: Option<ObjectID>,
+}
+
+/// The package's own modules from a build directory
+pub struct CompiledPackage {
+    pub name: String,
+    /// Module bytecode, sorted by filename
+    pub modules: Vec<Vec<u8>>,
+    /// Every dependency in `Move.lock`, empty without an environment or lock file
+    pub dependencies: Vec<PublishedDependency>,
+}
+
+#[derive(Deserialize)]
+struct BuildInfo {
+    compiled_package_info: CompiledPackageInfo,
+}
+
+#[derive(Deserialize)]
+struct CompiledPackageInfo {
+    package_name: String,
+    #[serde(default)]
+    build_flags: BuildFlags,
+}
+
+#[derive(Deserialize, Default)]
+struct BuildFlags {
+    #[serde(default)]
+    test_mode: bool,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: ManifestPackage,
+    #[serde(default)]
+    addresses: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ManifestPackage {
+    name: String,
+    published_at: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct MoveLock {
+    #[serde(default, rename = "move")]
+    move_section: LockMove,
+    #[serde(default)]
+    env: BTreeMap<String, LockEnv>,
+}
+
+#[derive(Deserialize, Default)]
+struct LockMove {
+    #[serde(default)]
+    package: Vec<LockPackage>,
+}
+
+#[derive(Deserialize)]
+struct LockPackage {
+    id: String,
+    source: LockSource,
+}
+
+#[derive(Deserialize)]
+struct LockSource {
+    local: Option<String>,
+    git: Option<String>,
+    rev: Option<String>,
+    subdir: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct LockEnv {
+    chain_id: Option<String>,
+    original_published_id: Option<String>,
+    latest_published_id: Option<String>,
+}
+
+/// The `build/<Pkg>/` directory of a package root or build directory, None for other paths
+pub fn find_build_dir(path: &Path) -> Result<Option<PathBuf>> {
+    if path.join(BUILD_INFO).is_file() {
+        return Ok(Some(path.to_path_buf()));
+    }
+    if !path.join(MANIFEST).is_file() {
+        return Ok(None);
+    }
+
+    let manifest = read_manifest(path)?;
+    let build_dir = path.join("build").join(&manifest.package.name);
+    if !build_dir.join(BUILD_INFO).is_file() {
+        anyhow::bail!(
+            "{} has no build output for {}, run `sui move build` first",
+            path.display(),
+            manifest.package.name
+        );
+    }
+    Ok(Some(build_dir))
+}
+
+/// Load a build directory's own modules and, for `env`, its dependencies' published IDs
+pub fn load_build_dir(build_dir: &Path, env: Option<&LockEnvironment>) -> Result<CompiledPackage> {
+    let info_path = build_dir.join(BUILD_INFO);
+    let info: BuildInfo = serde_yaml::from_str(&fs::read_to_string(&info_path)?)
+        .with_context(|| format!("Failed to parse {}", info_path.display()))?;
+    let info = info.compiled_package_info;
+    if info.build_flags.test_mode {
+        anyhow::bail!(
+            "{} was built in test mode, rebuild without --test",
+            info.package_name
+        );
+    }
+
+    // Dependency bytecode sits in bytecode_modules/dependencies/, which is skipped
+    let modules_dir = build_dir.join("bytecode_modules");
+    let mut paths: Vec<PathBuf> = fs::read_dir(&modules_dir)
+        .with_context(|| format!("Failed to read {}", modules_dir.display()))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "mv"))
+        .collect();
+    paths.sort();
+    if paths.is_empty() {
+        anyhow::bail!("No .mv files found in {}", modules_dir.display());
+    }
+    let modules = paths
+        .iter()
+        .map(|path| fs::read(path).with_context(|| format!("Failed to read {}", path.display())))
+        .collect::<Result<Vec<_>>>()?;
+
+    // build/<Pkg>/ is inside the package root, next to Move.lock
+    let dependencies = match (env, build_dir.parent().and_then(Path::parent)) {
+        (Some(env), Some(root)) => published_dependencies(root, env)?,
+        _ => Vec::new(),
+    };
+
+    Ok(CompiledPackage {
+        name: info.package_name,
+        modules,
+        dependencies,
+    })
+}
+
+/// Resolve every dependency in the root's `Move.lock` for `env`
+fn published_dependencies(root: &Path, env: &LockEnvironment) -> Result<Vec<PublishedDependency>> {
+    let Some(lock) = read_lock(root)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut dependencies = Vec::new();
+    for package in lock.move_section.package {
+        let (original_id, published_at) = match dependency_dir(root, &package.source) {
+            Some(dir) => published_ids(&dir, env)
+                .with_context(|| format!("Failed to resolve dependency {}", package.id))?,
+            None => (None, None),
+        };
+        dependencies.push(PublishedDependency {
+            name: package.id,
+            original_id,
+            published_at,
+        });
+    }
+    Ok(dependencies)
+}
+
+/// Source directory of a locked dependency: a local path or the Move git cache
+fn dependency_dir(root: &Path, source: &LockSource) -> Option<PathBuf> {
+    if let Some(local) = &source.local {
+        return Some(root.join(local));
+    }
+    let mut dir = move_home()?.join(git_cache_name(source.git.as_ref()?, source.rev.as_ref()?));
+    if let Some(subdir) = &source.subdir {
+        dir.push(subdir);
+    }
+    Some(dir)
+}
+
+/// `$MOVE_HOME`, by default `~/.move`
+fn move_home() -> Option<PathBuf> {
+    std::env::var_os("MOVE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".move")))
+}
+
+/// Directory name the Move package manager checks a git dependency out to
+fn git_cache_name(url: &str, rev: &str) -> String {
+    format!(
+        "{}_{}",
+        url.replace(['/', ':', '.', '@'], "_"),
+        rev.replace('/', "__")
+    )
+}
+
+/// Original and published ID of the package in `dir` for `env`
+///
+/// Automated address management records both in `Move.lock`; older packages set
+/// `published-at` in `Move.toml`, with the original ID under the named address that is, by
+/// convention, the lowercased package name. Missing files (e.g. a git dependency that was
+/// never fetched) leave the IDs unknown.
+fn published_ids(
+    dir: &Path,
+    env: &LockEnvironment,
+) -> Result<(Option<ObjectID>, Option<ObjectID>)> {
+    let entry = match read_lock(dir)? {
+        Some(lock) => env.select(lock.env)?,
+        None => None,
+    };
+    if let Some(entry) = entry
+        && let Some(latest) = entry.latest_published_id
+    {
+        let original = entry
+            .original_published_id
+            .map(|id| parse_id(&id))
+            .transpose()?;
+        return Ok((original, Some(parse_id(&latest)?)));
+    }
+    if !dir.join(MANIFEST).is_file() {
+        return Ok((None, None));
+    }
+    let mut manifest = read_manifest(dir)?;
+    let Some(published_at) = manifest.package.published_at else {
+        return Ok((None, None));
+    };
+    let original = manifest
+        .addresses
+        .remove(&manifest.package.name.to_lowercase())
+        .map(|id| parse_id(&id))
+        .transpose()?;
+    Ok((original, Some(parse_id(&published_at)?)))
+}
+
+fn read_manifest(dir: &Path) -> Result<Manifest> {
+    let path = dir.join(MANIFEST);
+    toml::from_str(&fs::read_to_string(&path)?)
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn read_lock(dir: &Path) -> Result<Option<MoveLock>> {
+    let path = dir.join(LOCK_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let lock = toml::from_str(&fs::read_to_string(&path)?)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(lock))
+}
+
+fn parse_id(id: &str) -> Result<ObjectID> {
+    ObjectID::from_str(id).with_context(|| format!("Invalid package ID {}", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = r#"
+[move]
+version = 3
+manifest_digest = "AB12"
+dependencies = [{ id = "Sui", name = "Sui" }, { id = "mylib", name = "mylib" }]
+
+[[move.package]]
+id = "Sui"
+source = { git = "https://github.com/MystenLabs/sui.git", rev = "framework/testnet", subdir = "crates/sui-framework/packages/sui-framework" }
+dependencies = [{ id = "MoveStdlib", name = "MoveStdlib" }]
+
+[[move.package]]
+id = "mylib"
+source = { local = "../mylib" }
+
+[env.testnet]
+chain-id = "4c78adac"
+original-published-id = "0xaa"
+latest-published-id = "0xbb"
+published-version = "2"
+"#;
+
+    #[test]
+    fn test_lock_parsing() {
+        let mut lock: MoveLock = toml::from_str(LOCK).unwrap();
+        let ids: Vec<_> = lock
+            .move_section
+            .package
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect();
+        assert_eq!(ids, ["Sui", "mylib"]);
+
+        let root = Path::new("/work/app");
+        assert_eq!(
+            dependency_dir(root, &lock.move_section.package[1].source),
+            Some(root.join("../mylib"))
+        );
+        assert_eq!(
+            git_cache_name("https://github.com/MystenLabs/sui.git", "framework/testnet"),
+            "https___github_com_MystenLabs_sui_git_framework__testnet"
+        );
+
+        let testnet = lock.env.remove("testnet").unwrap();
+        assert_eq!(testnet.chain_id.as_deref(), Some("4c78adac"));
+        assert_eq!(testnet.original_published_id.as_deref(), Some("0xaa"));
+        assert_eq!(testnet.latest_published_id.as_deref(), Some("0xbb"));
+    }
+
+    const MAINNET: &str = "35834a8a";
+
+    fn build_info(test_mode: bool) -> String {
+        format!(
+            "---\ncompiled_package_info:\n  package_name: App\n  build_flags:\n    \
+             dev_mode: false\n    test_mode: {}\n",
+            test_mode
+        )
+    }
+
+    /// `app/` with its build output and a local dependency `mylib/` published on two chains
+    fn write_packages(dir: &Path) -> PathBuf {
+        let root = dir.join("app");
+        let build_dir = root.join("build/App");
+        let modules_dir = build_dir.join("bytecode_modules");
+        fs::create_dir_all(modules_dir.join("dependencies/Sui")).unwrap();
+        fs::create_dir_all(dir.join("mylib")).unwrap();
+
+        fs::write(root.join(MANIFEST), "[package]\nname = \"App\"\n").unwrap();
+        fs::write(
+            root.join(LOCK_FILE),
+            "[[move.package]]\nid = \"mylib\"\nsource = { local = \"../mylib\" }\n",
+        )
+        .unwrap();
+        fs::write(build_dir.join(BUILD_INFO), build_info(false)).unwrap();
+        fs::write(modules_dir.join("b.mv"), [2]).unwrap();
+        fs::write(modules_dir.join("a.mv"), [1]).unwrap();
+        fs::write(modules_dir.join("dependencies/Sui/coin.mv"), [9]).unwrap();
+
+        fs::write(
+            dir.join("mylib").join(LOCK_FILE),
+            format!(
+                "[env.testnet]\nchain-id = \"4c78adac\"\noriginal-published-id = \"0xaa\"\n\
+                 latest-published-id = \"0xbb\"\n\n[env.mainnet]\nchain-id = \"{}\"\n\
+                 original-published-id = \"0xcc\"\nlatest-published-id = \"0xdd\"\n",
+                MAINNET
+            ),
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_load_build_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = write_packages(dir.path());
+        let build_dir = root.join("build/App");
+
+        assert_eq!(find_build_dir(&root).unwrap(), Some(build_dir.clone()));
+        assert_eq!(find_build_dir(&build_dir).unwrap(), Some(build_dir.clone()));
+        assert_eq!(find_build_dir(&dir.path().join("mylib")).unwrap(), None);
+
+        // The environment is picked by the chain's ID, whatever --env would have defaulted to
+        let mainnet = LockEnvironment {
+            name: None,
+            chain_id: Some(MAINNET.to_string()),
+        };
+        let package = load_build_dir(&build_dir, Some(&mainnet)).unwrap();
+        assert_eq!(package.name, "App");
+        assert_eq!(package.modules, vec![vec![1], vec![2]]);
+        assert_eq!(package.dependencies.len(), 1);
+        let mylib = &package.dependencies[0];
+        assert_eq!(mylib.name, "mylib");
+        assert_eq!(mylib.original_id, Some(ObjectID::from_str("0xcc").unwrap()));
+        assert_eq!(
+            mylib.published_at,
+            Some(ObjectID::from_str("0xdd").unwrap())
+        );
+
+        // A named environment on another chain than the RPC's is refused
+        let testnet_on_mainnet = LockEnvironment {
+            name: Some("testnet".to_string()),
+            chain_id: Some(MAINNET.to_string()),
+        };
+        let err = load_build_dir(&build_dir, Some(&testnet_on_mainnet))
+            .err()
+            .unwrap();
+        assert!(
+            format!("{:#}", err).contains("is chain 4c78adac"),
+            "{:#}",
+            err
+        );
+
+        fs::write(build_dir.join(BUILD_INFO), build_info(true)).unwrap();
+        let err = load_build_dir(&build_dir, None).err().unwrap();
+        assert!(err.to_string().contains("test mode"), "{}", err);
+    }
+}
@@ -0,0 +1,111 @@
+//! Publish dependencies of a compiled package
+//!
+//! A publish must list every package its modules link against. The direct ones are the
+//! addresses of the module handles other than the package's own; the framework packages
+//! (`0x1`, `0x2`) are always added, like `sui client publish` does. Transitive dependencies
+//! and the current versions of upgraded libraries aren't in the bytecode and have to be
+//! supplied by the user.
+//!
+//! Publishing also runs each module's `init`, whose objects are created ahead of the
+//! UpgradeCap.
+
+use crate::module_order::deserialize_modules;
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use sui_types::base_types::ObjectID;
+use sui_types::{MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID};
+
+/// Packages referenced by the modules' handles, plus the framework packages
+pub fn referenced_packages(modules: &[Vec<u8>]) -> Result<BTreeSet<ObjectID>> {
+    let compiled = deserialize_modules(modules)?;
+
+    let own: BTreeSet<_> = compiled.iter().map(|m| *m.address()).collect();
+    let mut packages = BTreeSet::from([MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID]);
+    for module in &compiled {
+        packages.extend(
+            module
+                .module_handles()
+                .iter()
+                .map(|handle| *module.address_identifier_at(handle.address))
+                .filter(|address| !own.contains(address))
+                .map(ObjectID::from),
+        );
+    }
+    Ok(packages)
+}
+
+/// Whether any module defines an `init` function
+pub fn defines_init(modules: &[Vec<u8>]) -> Result<bool> {
+    let compiled = deserialize_modules(modules)?;
+    Ok(compiled.iter().any(|module| {
+        module.function_defs().iter().any(|def| {
+            let handle = module.function_handle_at(def.function);
+            module.identifier_at(handle.name).as_str() == "init"
+        })
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::{
+        AddressIdentifierIndex, FunctionDefinition, FunctionHandle, FunctionHandleIndex,
+        IdentifierIndex, ModuleHandle, ModuleHandleIndex, SignatureIndex, Visibility, empty_module,
+    };
+    use std::str::FromStr;
+    use sui_types::Identifier;
+
+    #[test]
+    fn test_collects_non_self_addresses() {
+        let deepbook = ObjectID::from_str("0xdee9").unwrap();
+        let mut module = empty_module();
+        module.identifiers[0] = Identifier::new("market").unwrap();
+        module.identifiers.push(Identifier::new("clob").unwrap());
+        module.address_identifiers.push(deepbook.into());
+        module.module_handles.push(ModuleHandle {
+            address: AddressIdentifierIndex(1),
+            name: IdentifierIndex(1),
+        });
+        let mut bytes = Vec::new();
+        module
+            .serialize_with_version(module.version, &mut bytes)
+            .unwrap();
+
+        let packages = referenced_packages(&[bytes]).unwrap();
+        assert_eq!(
+            packages.into_iter().collect::<Vec<_>>(),
+            [MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID, deepbook]
+        );
+    }
+
+    #[test]
+    fn test_defines_init() {
+        let mut module = empty_module();
+        let serialize = |module: &move_binary_format::CompiledModule| {
+            let mut bytes = Vec::new();
+            module
+                .serialize_with_version(module.version, &mut bytes)
+                .unwrap();
+            bytes
+        };
+        assert!(!defines_init(&[serialize(&module)]).unwrap());
+
+        module.identifiers.push(Identifier::new("init").unwrap());
+        module.function_handles.push(FunctionHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(1),
+            parameters: SignatureIndex(0),
+            return_: SignatureIndex(0),
+            type_parameters: vec![],
+        });
+        module.function_defs.push(FunctionDefinition {
+            function: FunctionHandleIndex(0),
+            visibility: Visibility::Private,
+            is_entry: false,
+            acquires_global_resources: vec![],
+            code: None,
+        });
+        assert!(defines_init(&[serialize(&module)]).unwrap());
+    }
+}
@@ -1,16 +1,21 @@
+use crate::estimate::Estimate;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 /// Progress display manager
 pub struct ProgressDisplay {
     bar: ProgressBar,
     start_time: Instant,
-    estimated_attempts: u64,
+    /// Success probability of the search, None if unknown
+    estimate: Option<Estimate>,
+    /// Replaces the odds once there is something to report (best score, matches found)
+    status: Mutex<Option<String>>,
 }
 
 impl ProgressDisplay {
-    pub fn new(estimated_attempts: u64, prefix: &str) -> Self {
-        let bar = ProgressBar::new(estimated_attempts);
+    pub fn new(estimate: Option<Estimate>, prefix: &str) -> Self {
+        let bar = ProgressBar::new(estimate.map_or(0, |e| e.mean_attempts() as u64));
         
         let style = ProgressStyle::default_bar()
             .template("{spinner:.green} [{elapsed_precise}] {msg}")
@@ -23,7 +28,8 @@ impl ProgressDisplay {
         Self {
             bar,
             start_time: Instant::now(),
-            estimated_attempts,
+            estimate,
+            status: Mutex::new(None),
         }
     }
 
@@ -36,23 +42,48 @@ impl ProgressDisplay {
         };
 
         let hashrate_str = format_hashrate(hashrate);
-        let eta = if hashrate > 0.0 {
-            let remaining = self.estimated_attempts.saturating_sub(attempts);
-            let eta_secs = remaining as f64 / hashrate;
-            format_duration(Duration::from_secs_f64(eta_secs))
-        } else {
-            "calculating...".to_string()
+        // Attempts are independent trials, so report the chance of having found a match
+        // by now rather than a linear ETA that goes negative once past the average
+        let odds = match &self.estimate {
+            Some(estimate) => {
+                let found = estimate.found_by(attempts);
+                let target_attempts = estimate.attempts_for(0.9);
+                let eta = Estimate::time_for(target_attempts.saturating_sub(attempts), hashrate);
+                match eta {
+                    Some(eta) if attempts < target_attempts => format!(
+                        "P(found): {:.1}% | 90% in {}",
+                        found * 100.0,
+                        format_duration(eta)
+                    ),
+                    _ => format!("P(found): {:.1}%", found * 100.0),
+                }
+            }
+            None => "ETA: unknown".to_string(),
         };
 
+        let status = self.status.lock().unwrap().clone().unwrap_or(odds);
+
         self.bar.set_message(format!(
-            "{} | {} attempts | ETA: {}",
+            "{} | {} attempts | {}",
             hashrate_str,
             format_number(attempts),
-            eta
+            status
         ));
         self.bar.tick();
     }
 
+    /// Print an improved score-mining result above the bar and keep it in the status line
+    pub fn report_best(&self, summary: &str, details: &str) {
+        self.bar.println(details);
+        *self.status.lock().unwrap() = Some(format!("Best: {}", summary));
+    }
+
+    /// Print a streamed match to stdout without tearing the bar and count it in the status
+    pub fn report_match(&self, line: &str, found: u64) {
+        self.bar.suspend(|| println!("{}", line));
+        *self.status.lock().unwrap() = Some(format!("Found: {}", found));
+    }
+
     #[allow(dead_code)]
     pub fn finish_with_success(&self, attempts: u64) {
         let elapsed = self.start_time.elapsed();
@@ -94,7 +125,7 @@ fn format_number(n: u64) -> String {
     }
 }
 
-fn format_duration(duration: Duration) -> String {
+pub fn format_duration(duration: Duration) -> String {
     let secs = duration.as_secs();
     if secs >= 3600 {
         format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
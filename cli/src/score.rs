@@ -0,0 +1,196 @@
+//! Scoring functions for "best-so-far" mining
+//!
+//! Instead of an exact target, a `Scorer` rates every derived ID so the miner can keep
+//! the best ones found within a time or attempt budget.
+
+use anyhow::Result;
+use std::fmt;
+
+const ID_NIBBLES: usize = 64;
+
+/// Rates an Object ID; higher is better
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Scorer {
+    /// Number of leading `0` nibbles
+    LeadingZeros,
+    /// Length of the longest run of a single repeated nibble anywhere in the ID
+    RepeatRun,
+    /// Number of leading hex chars covered by a chain of dictionary words
+    /// (e.g. `dead`, `beef` scores 8 on `0xdeadbeef…`)
+    Dictionary(Vec<Vec<u8>>),
+}
+
+impl Scorer {
+    /// Parse a scorer by name (`leading-zeros`, `repeat-run` or `dictionary`)
+    pub fn from_name(name: &str, words: &[String]) -> Result<Self> {
+        match name {
+            "leading-zeros" | "zeros" => Ok(Self::LeadingZeros),
+            "repeat-run" | "repeat" => Ok(Self::RepeatRun),
+            "dictionary" | "dict" => Self::dictionary(words),
+            other => anyhow::bail!(
+                "Unknown score '{}' (expected leading-zeros, repeat-run or dictionary)",
+                other
+            ),
+        }
+    }
+
+    /// Dictionary scorer over the given hex words
+    pub fn dictionary<S: AsRef<str>>(words: &[S]) -> Result<Self> {
+        let mut parsed = Vec::with_capacity(words.len());
+        for word in words {
+            let word = word.as_ref().trim();
+            let word = word.strip_prefix("0x").unwrap_or(word).to_lowercase();
+            if word.is_empty() {
+                continue;
+            }
+            if word.len() > ID_NIBBLES {
+                anyhow::bail!("Dictionary word too long: {}", word);
+            }
+            let nibbles = word
+                .chars()
+                .map(|c| {
+                    c.to_digit(16)
+                        .map(|d| d as u8)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid hex in dictionary word: {}", word))
+                })
+                .collect::<Result<Vec<u8>>>()?;
+            parsed.push(nibbles);
+        }
+        if parsed.is_empty() {
+            anyhow::bail!("Dictionary score needs at least one word (use --words)");
+        }
+        parsed.sort();
+        parsed.dedup();
+        Ok(Self::Dictionary(parsed))
+    }
+
+    /// Score an Object ID
+    #[inline]
+    pub fn score(&self, id: &[u8; 32]) -> u32 {
+        match self {
+            Self::LeadingZeros => {
+                let mut zeros = 0;
+                for &byte in id {
+                    if byte == 0 {
+                        zeros += 2;
+                    } else {
+                        if byte < 0x10 {
+                            zeros += 1;
+                        }
+                        break;
+                    }
+                }
+                zeros
+            }
+            Self::RepeatRun => {
+                let mut best = 1;
+                let mut run = 1;
+                for i in 1..ID_NIBBLES {
+                    if nibble(id, i) == nibble(id, i - 1) {
+                        run += 1;
+                        best = best.max(run);
+                    } else {
+                        run = 1;
+                    }
+                }
+                best
+            }
+            Self::Dictionary(words) => {
+                // reachable[i]: the first i nibbles can be tiled with dictionary words
+                let mut reachable = [false; ID_NIBBLES + 1];
+                reachable[0] = true;
+                let mut best = 0;
+                for start in 0..ID_NIBBLES {
+                    if !reachable[start] {
+                        continue;
+                    }
+                    for word in words {
+                        let end = start + word.len();
+                        if end <= ID_NIBBLES
+                            && !reachable[end]
+                            && word
+                                .iter()
+                                .enumerate()
+                                .all(|(i, &n)| nibble(id, start + i) == n)
+                        {
+                            reachable[end] = true;
+                            best = best.max(end);
+                        }
+                    }
+                }
+                best as u32
+            }
+        }
+    }
+}
+
+#[inline(always)]
+fn nibble(id: &[u8; 32], pos: usize) -> u8 {
+    let byte = id[pos / 2];
+    if pos.is_multiple_of(2) {
+        byte >> 4
+    } else {
+        byte & 0x0f
+    }
+}
+
+impl fmt::Display for Scorer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LeadingZeros => write!(f, "leading zeros"),
+            Self::RepeatRun => write!(f, "longest repeated-nibble run"),
+            Self::Dictionary(words) => write!(f, "dictionary chars ({} words)", words.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id_from_hex(hex_str: &str) -> [u8; 32] {
+        let padded = format!("{:0<64}", hex_str);
+        let mut id = [0u8; 32];
+        hex::decode_to_slice(padded, &mut id).unwrap();
+        id
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        let scorer = Scorer::LeadingZeros;
+        assert_eq!(scorer.score(&id_from_hex("000a1f")), 3);
+        assert_eq!(scorer.score(&id_from_hex("00001f")), 4);
+        assert_eq!(scorer.score(&id_from_hex("f")), 0);
+        assert_eq!(scorer.score(&[0u8; 32]), 64);
+    }
+
+    #[test]
+    fn test_repeat_run() {
+        let scorer = Scorer::RepeatRun;
+        let id = id_from_hex(&format!("1f7777712f{}", "12".repeat(27)));
+        assert_eq!(scorer.score(&id), 5);
+        // Zero padding at the end is a run of 54
+        assert_eq!(scorer.score(&id_from_hex("1f7777712f")), 54);
+        assert_eq!(scorer.score(&[0x12; 32]), 1);
+    }
+
+    #[test]
+    fn test_dictionary_chains_words() {
+        let scorer = Scorer::dictionary(&["dead", "beef", "ca", "feed", "cafe"]).unwrap();
+        assert_eq!(scorer.score(&id_from_hex("deadbeef12")), 8);
+        // "cafe" then "ed" dead-ends, "ca" + "feed" covers 6
+        assert_eq!(scorer.score(&id_from_hex("cafeed12")), 6);
+        assert_eq!(scorer.score(&id_from_hex("12deadbeef")), 0);
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            Scorer::from_name("leading-zeros", &[]).unwrap(),
+            Scorer::LeadingZeros
+        );
+        assert!(Scorer::from_name("dictionary", &[]).is_err());
+        assert!(Scorer::from_name("dictionary", &["xyz".to_string()]).is_err());
+        assert!(Scorer::from_name("unknown", &[]).is_err());
+    }
+}
@@ -1,9 +1,15 @@
 //! WebSocket Server for Web Mining Interface
 
+use crate::address::KeyScheme;
+use crate::address::split::{self, SplitKeyMode};
+use crate::mining::mode::{MiningMode, MiningResult, ObjectMatch};
 use crate::mining::{
-    CpuExecutor, GasCoinMode, MinerConfig, MinerExecutor, PackageMode, SingleObjectMode,
+    BestSearch, CpuExecutor, CreatedObjectsMode, DigestMode, GasCoinMode, MinerConfig,
+    MinerExecutor, NonceStrategy, ObjectRangeMode, PackageMode, ScoredResult, SingleObjectMode,
+    search,
 };
 use crate::module_order::sort_modules_by_dependency;
+use crate::package_deps::defines_init;
 use crate::target::TargetChecker;
 
 use anyhow::{Context, Result};
@@ -22,11 +28,11 @@ use tokio_tungstenite::{accept_async, tungstenite::Message};
 use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
 
 use crate::common::{
-    create_split_tx_template, create_template_from_bytes, create_tx_template, format_large_number,
+    DependencySpec, IndexSpec, MiningGoal, NonceSpec, ObjectIndices, ObjectsSpec, ScoreSpec,
+    StreamSpec, TargetSpec, check_scorable, create_split_tx_template, create_template_from_bytes,
+    create_tx_template, create_upgrade_tx_template, format_large_number, random_start_nonce,
     randomize_gas_budget,
 };
-use rand::Rng;
-use rand::rngs::OsRng;
 
 /// Message from Web Client
 #[derive(Debug, Deserialize)]
@@ -34,30 +40,84 @@ use rand::rngs::OsRng;
 pub enum ClientMessage {
     #[serde(rename = "start_package_mining")]
     StartPackageMining {
-        prefix: String,
+        #[serde(flatten)]
+        target: TargetSpec,
+        #[serde(flatten)]
+        score: ScoreSpec,
+        /// Optional streaming ("all", "count"): every match is sent as its own found event
+        #[serde(flatten)]
+        stream: StreamSpec,
+        /// Optional nonce field ("nonce" as "epoch", "gas-budget" with "max_gas_budget",
+        /// "gas-price" with "max_gas_price" or "input"), epoch by default
+        #[serde(flatten)]
+        nonce: NonceSpec,
+        /// Optional created-object targets ("object_index", "objects" as ["INDEX:PATTERN"],
+        /// "cap_prefix"/"cap_pattern", plus "cap_index" when a module defines init)
+        #[serde(flatten)]
+        objects: ObjectsSpec,
+        /// Optional "dependencies" (package IDs added to the ones the bytecode references)
+        /// and "exact_dependencies" (use only the given ones)
+        #[serde(flatten)]
+        dependencies: DependencySpec,
         modules_base64: Vec<String>,
-        sender: String,
-        gas_budget: u64,
-        gas_price: u64,
-        gas_object_id: String,
-        gas_object_version: u64,
-        gas_object_digest: String,
+        /// "sender", "gas_budget", "gas_price" and the gas object reference
+        #[serde(flatten)]
+        gas: GasSpec,
         threads: Option<usize>,
         #[serde(default)]
         nonce_offset: u64, // Resume from this nonce
         #[serde(default)]
         gpu: bool,
     },
+    /// Mine the new package ID of an upgrade; results arrive as `package_found`
+    #[serde(rename = "start_upgrade_mining")]
+    StartUpgradeMining {
+        #[serde(flatten)]
+        target: TargetSpec,
+        #[serde(flatten)]
+        score: ScoreSpec,
+        #[serde(flatten)]
+        stream: StreamSpec,
+        #[serde(flatten)]
+        nonce: NonceSpec,
+        modules_base64: Vec<String>,
+        /// Current package ID being upgraded
+        package_id: String,
+        /// Optional "dependencies" and "exact_dependencies", as for a publish
+        #[serde(flatten)]
+        dependencies: DependencySpec,
+        /// Upgrade policy to authorize (0 = compatible)
+        #[serde(default)]
+        upgrade_policy: u8,
+        /// "upgrade_cap_id", "upgrade_cap_version" and "upgrade_cap_digest"
+        #[serde(flatten)]
+        upgrade_cap: UpgradeCapSpec,
+        /// "sender", "gas_budget", "gas_price" and the gas object reference
+        #[serde(flatten)]
+        gas: GasSpec,
+        threads: Option<usize>,
+        #[serde(default)]
+        nonce_offset: u64,
+        #[serde(default)]
+        gpu: bool,
+    },
     #[serde(rename = "start_gas_coin_mining")]
     StartGasCoinMining {
-        prefix: String,
+        #[serde(flatten)]
+        target: TargetSpec,
+        #[serde(flatten)]
+        score: ScoreSpec,
+        #[serde(flatten)]
+        stream: StreamSpec,
+        #[serde(flatten)]
+        nonce: NonceSpec,
         split_amounts: Vec<u64>,
-        sender: String,
-        gas_budget: u64,
-        gas_price: u64,
-        gas_object_id: String,
-        gas_object_version: u64,
-        gas_object_digest: String,
+        /// Require at least this many split coins to match (k of N, default 1)
+        #[serde(default)]
+        min_matches: u16,
+        /// "sender", "gas_budget", "gas_price" and the gas object reference
+        #[serde(flatten)]
+        gas: GasSpec,
         threads: Option<usize>,
         #[serde(default)]
         nonce_offset: u64,
@@ -66,20 +126,100 @@ pub enum ClientMessage {
     },
     #[serde(rename = "start_move_call_mining")]
     StartMoveCallMining {
-        prefix: String,
+        #[serde(flatten)]
+        target: TargetSpec,
+        #[serde(flatten)]
+        score: ScoreSpec,
+        #[serde(flatten)]
+        stream: StreamSpec,
+        #[serde(flatten)]
+        nonce: NonceSpec,
         tx_bytes_base64: String,
-        #[serde(default)]
-        object_index: u16,
+        /// "object_index" for an exact index or "index_range" ("0..8") to check several
+        #[serde(flatten)]
+        indices: IndexSpec,
         threads: Option<usize>,
         #[serde(default)]
         nonce_offset: u64,
         #[serde(default)]
         gpu: bool,
     },
+    /// Mine a vanity Base58 transaction digest for any transaction
+    #[serde(rename = "start_digest_mining")]
+    StartDigestMining {
+        /// Base58 prefix of the digest
+        prefix: String,
+        #[serde(default)]
+        ignore_case: bool,
+        #[serde(flatten)]
+        stream: StreamSpec,
+        #[serde(flatten)]
+        nonce: NonceSpec,
+        tx_bytes_base64: String,
+        threads: Option<usize>,
+        #[serde(default)]
+        nonce_offset: u64,
+    },
+    /// Mine an offset for a requester's public key; the server never sees the private key
+    #[serde(rename = "start_split_key_mining")]
+    StartSplitKeyMining {
+        #[serde(flatten)]
+        target: TargetSpec,
+        public_key: String,
+        #[serde(default = "default_split_scheme")]
+        scheme: KeyScheme,
+        threads: Option<usize>,
+    },
     #[serde(rename = "stop_mining")]
     StopMining,
 }
 
+/// Sender and gas payment of a transaction the server builds
+#[derive(Debug, Deserialize)]
+pub struct GasSpec {
+    sender: String,
+    gas_budget: u64,
+    gas_price: u64,
+    gas_object_id: String,
+    gas_object_version: u64,
+    gas_object_digest: String,
+}
+
+impl GasSpec {
+    /// Parse the sender and the gas object reference
+    fn build(&self) -> Result<(SuiAddress, (ObjectID, SequenceNumber, ObjectDigest))> {
+        use std::str::FromStr;
+
+        let sender = SuiAddress::from_str(&self.sender).context("Invalid sender")?;
+        let gas_payment = parse_object_ref(
+            &self.gas_object_id,
+            self.gas_object_version,
+            &self.gas_object_digest,
+            "gas object",
+        )?;
+        Ok((sender, gas_payment))
+    }
+}
+
+/// The UpgradeCap authorizing an upgrade, sent as ID, version and Base58 digest
+#[derive(Debug, Deserialize)]
+pub struct UpgradeCapSpec {
+    upgrade_cap_id: String,
+    upgrade_cap_version: u64,
+    upgrade_cap_digest: String,
+}
+
+impl UpgradeCapSpec {
+    fn build(&self) -> Result<(ObjectID, SequenceNumber, ObjectDigest)> {
+        parse_object_ref(
+            &self.upgrade_cap_id,
+            self.upgrade_cap_version,
+            &self.upgrade_cap_digest,
+            "UpgradeCap",
+        )
+    }
+}
+
 /// Message to Web Client
 #[derive(Debug, Serialize)]
 #[serde(tag = "type")]
@@ -91,9 +231,13 @@ pub enum ServerMessage {
     MiningStarted {
         mode: String,
         prefix: String,
+        pattern: String,
         difficulty: usize,
         estimated_attempts: u64,
         threads: usize,
+        /// Transaction field varied while mining, its value is sent as `nonce_value`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nonce_field: Option<String>,
     },
 
     #[serde(rename = "progress")]
@@ -102,39 +246,156 @@ pub enum ServerMessage {
     #[serde(rename = "package_found")]
     PackageFound {
         package_id: String,
+        matched_pattern: String,
+        /// The object at the main index and every extra object or UpgradeCap target
+        matches: Vec<MatchedObject>,
         tx_digest: String,
         tx_bytes_base64: String,
         attempts: u64,
-        gas_budget_used: u64,
+        #[serde(flatten)]
+        nonce_value: NonceValue,
     },
 
     #[serde(rename = "gas_coin_found")]
     GasCoinFound {
         object_id: String,
         object_index: u16,
+        matched_pattern: String,
+        /// Every matching coin (more than one in k-of-N mining)
+        matches: Vec<MatchedObject>,
+        tx_digest: String,
+        tx_bytes_base64: String,
+        attempts: u64,
+        #[serde(flatten)]
+        nonce_value: NonceValue,
+    },
+
+    #[serde(rename = "digest_found")]
+    DigestFound {
         tx_digest: String,
+        matched_pattern: String,
         tx_bytes_base64: String,
         attempts: u64,
-        gas_budget_used: u64,
+        #[serde(flatten)]
+        nonce_value: NonceValue,
     },
 
     #[serde(rename = "move_call_found")]
     MoveCallFound {
         object_id: String,
         object_index: u16,
+        matched_pattern: String,
         tx_digest: String,
         tx_bytes_base64: String,
         attempts: u64,
-        gas_budget_used: u64,
+        #[serde(flatten)]
+        nonce_value: NonceValue,
     },
 
+    #[serde(rename = "split_key_found")]
+    SplitKeyFound {
+        address: String,
+        offset: String,
+        scheme: String,
+        matched_pattern: String,
+        attempts: u64,
+    },
+
+    /// Score mining found a new best ID
+    #[serde(rename = "score_improved")]
+    ScoreImproved {
+        #[serde(flatten)]
+        entry: ScoredEntry,
+        attempts: u64,
+    },
+
+    /// Score mining finished, best first
+    #[serde(rename = "best_results")]
+    BestResults {
+        results: Vec<ScoredEntry>,
+        attempts: u64,
+    },
+
+    /// `exhausted`: a windowed nonce (gas budget or price) tried every value, so resuming
+    /// from `last_nonce` can't find anything new
     #[serde(rename = "stopped")]
-    Stopped { attempts: u64, last_nonce: u64 },
+    Stopped {
+        attempts: u64,
+        last_nonce: u64,
+        exhausted: bool,
+    },
 
     #[serde(rename = "error")]
     Error { message: String },
 }
 
+fn default_split_scheme() -> KeyScheme {
+    KeyScheme::Secp256k1
+}
+
+/// One matching object of a k-of-N result
+#[derive(Debug, Serialize)]
+pub struct MatchedObject {
+    object_id: String,
+    object_index: u16,
+    matched_pattern: String,
+}
+
+impl MatchedObject {
+    fn new(matched: &ObjectMatch, target: &TargetChecker) -> Self {
+        Self {
+            object_id: format!("0x{}", hex::encode(matched.object_id.as_ref())),
+            object_index: matched.object_index,
+            matched_pattern: target.pattern_label(matched.pattern_index),
+        }
+    }
+}
+
+/// Value written into the nonce field of a result
+///
+/// Also sent as `gas_budget_used`, its name from when only the gas budget carried the nonce,
+/// so older clients keep working; that key is deprecated and will be dropped.
+#[derive(Debug, Serialize)]
+pub struct NonceValue {
+    nonce_value: u64,
+    gas_budget_used: u64,
+}
+
+impl From<u64> for NonceValue {
+    fn from(value: u64) -> Self {
+        Self {
+            nonce_value: value,
+            gas_budget_used: value,
+        }
+    }
+}
+
+/// A score-mining result as sent to the Web Client
+#[derive(Debug, Serialize)]
+pub struct ScoredEntry {
+    object_id: String,
+    object_index: u16,
+    score: u32,
+    tx_digest: String,
+    tx_bytes_base64: String,
+    #[serde(flatten)]
+    nonce_value: NonceValue,
+}
+
+impl From<&ScoredResult> for ScoredEntry {
+    fn from(scored: &ScoredResult) -> Self {
+        let res = &scored.result;
+        Self {
+            object_id: format!("0x{}", hex::encode(res.object_id.as_ref())),
+            object_index: res.object_index,
+            score: scored.score,
+            tx_digest: res.tx_digest.to_string(),
+            tx_bytes_base64: general_purpose::STANDARD.encode(&res.tx_bytes),
+            nonce_value: res.nonce_value.into(),
+        }
+    }
+}
+
 pub async fn run_server(port: u16, default_modules: Option<Vec<Vec<u8>>>) -> Result<()> {
     let addr = format!("127.0.0.1:{}", port);
     let listener = TcpListener::bind(&addr).await?;
@@ -202,14 +463,14 @@ async fn handle_connection(
                 let text_str: &str = &text;
                 match serde_json::from_str::<ClientMessage>(text_str) {
                     Ok(ClientMessage::StartPackageMining {
-                        prefix,
+                        target,
+                        score,
+                        stream,
+                        nonce,
+                        objects,
+                        dependencies,
                         modules_base64,
-                        sender,
-                        gas_budget,
-                        gas_price,
-                        gas_object_id,
-                        gas_object_version,
-                        gas_object_digest,
+                        gas,
                         threads,
                         nonce_offset,
                         gpu,
@@ -268,35 +529,113 @@ async fn handle_connection(
 
                         tokio::task::spawn_blocking(move || {
                             let result = run_package_mining(
-                                prefix,
+                                target,
+                                score,
+                                stream,
+                                nonce,
+                                objects,
+                                dependencies,
                                 sorted_modules,
-                                sender,
-                                gas_budget,
-                                gas_price,
-                                gas_object_id,
-                                gas_object_version,
-                                gas_object_digest,
+                                gas,
                                 thread_count,
                                 nonce_offset,
                                 gpu,
                                 cancel_clone,
-                                out_tx_clone,
+                                out_tx_clone.clone(),
                             );
 
                             if let Err(e) = result {
                                 eprintln!("Package mining error: {}", e);
+                                let _ = out_tx_clone.blocking_send(ServerMessage::Error {
+                                    message: e.to_string(),
+                                });
+                            }
+                        });
+                    }
+                    Ok(ClientMessage::StartUpgradeMining {
+                        target,
+                        score,
+                        stream,
+                        nonce,
+                        modules_base64,
+                        package_id,
+                        dependencies,
+                        upgrade_policy,
+                        upgrade_cap,
+                        gas,
+                        threads,
+                        nonce_offset,
+                        gpu,
+                    }) => {
+                        let modules = modules_base64
+                            .iter()
+                            .filter_map(|b64| general_purpose::STANDARD.decode(b64).ok())
+                            .collect::<Vec<Vec<u8>>>();
+                        if modules.is_empty() {
+                            let _ = out_tx
+                                .send(ServerMessage::Error {
+                                    message: "An upgrade needs the new modules".to_string(),
+                                })
+                                .await;
+                            continue;
+                        }
+
+                        let sorted_modules = if modules.len() > 1 {
+                            match sort_modules_by_dependency(modules) {
+                                Ok(sorted) => sorted,
+                                Err(e) => {
+                                    let _ = out_tx
+                                        .send(ServerMessage::Error {
+                                            message: format!("Failed to sort modules: {}", e),
+                                        })
+                                        .await;
+                                    continue;
+                                }
+                            }
+                        } else {
+                            modules
+                        };
+
+                        cancel.store(false, Ordering::SeqCst);
+                        let cancel_clone = cancel.clone();
+                        let out_tx_clone = out_tx.clone();
+                        let thread_count = threads.unwrap_or_else(num_cpus::get);
+
+                        tokio::task::spawn_blocking(move || {
+                            let result = run_upgrade_mining(
+                                target,
+                                score,
+                                stream,
+                                nonce,
+                                sorted_modules,
+                                package_id,
+                                dependencies,
+                                upgrade_policy,
+                                upgrade_cap,
+                                gas,
+                                thread_count,
+                                nonce_offset,
+                                gpu,
+                                cancel_clone,
+                                out_tx_clone.clone(),
+                            );
+
+                            if let Err(e) = result {
+                                eprintln!("Upgrade mining error: {}", e);
+                                let _ = out_tx_clone.blocking_send(ServerMessage::Error {
+                                    message: e.to_string(),
+                                });
                             }
                         });
                     }
                     Ok(ClientMessage::StartGasCoinMining {
-                        prefix,
+                        target,
+                        score,
+                        stream,
+                        nonce,
                         split_amounts,
-                        sender,
-                        gas_budget,
-                        gas_price,
-                        gas_object_id,
-                        gas_object_version,
-                        gas_object_digest,
+                        min_matches,
+                        gas,
                         threads,
                         nonce_offset,
                         gpu,
@@ -317,30 +656,35 @@ async fn handle_connection(
 
                         tokio::task::spawn_blocking(move || {
                             let result = run_gas_coin_mining(
-                                prefix,
+                                target,
+                                score,
+                                stream,
+                                nonce,
                                 split_amounts,
-                                sender,
-                                gas_budget,
-                                gas_price,
-                                gas_object_id,
-                                gas_object_version,
-                                gas_object_digest,
+                                min_matches,
+                                gas,
                                 thread_count,
                                 nonce_offset,
                                 gpu,
                                 cancel_clone,
-                                out_tx_clone,
+                                out_tx_clone.clone(),
                             );
 
                             if let Err(e) = result {
                                 eprintln!("Gas coin mining error: {}", e);
+                                let _ = out_tx_clone.blocking_send(ServerMessage::Error {
+                                    message: e.to_string(),
+                                });
                             }
                         });
                     }
                     Ok(ClientMessage::StartMoveCallMining {
-                        prefix,
+                        target,
+                        score,
+                        stream,
+                        nonce,
                         tx_bytes_base64,
-                        object_index,
+                        indices,
                         threads,
                         nonce_offset,
                         gpu,
@@ -352,18 +696,88 @@ async fn handle_connection(
 
                         tokio::task::spawn_blocking(move || {
                             let result = run_move_call_mining(
-                                prefix,
+                                target,
+                                score,
+                                stream,
+                                nonce,
                                 tx_bytes_base64,
-                                object_index,
+                                indices,
                                 thread_count,
                                 nonce_offset,
                                 gpu,
                                 cancel_clone,
-                                out_tx_clone,
+                                out_tx_clone.clone(),
                             );
 
                             if let Err(e) = result {
                                 eprintln!("Move Call mining error: {}", e);
+                                let _ = out_tx_clone.blocking_send(ServerMessage::Error {
+                                    message: e.to_string(),
+                                });
+                            }
+                        });
+                    }
+                    Ok(ClientMessage::StartDigestMining {
+                        prefix,
+                        ignore_case,
+                        stream,
+                        nonce,
+                        tx_bytes_base64,
+                        threads,
+                        nonce_offset,
+                    }) => {
+                        cancel.store(false, Ordering::SeqCst);
+                        let cancel_clone = cancel.clone();
+                        let out_tx_clone = out_tx.clone();
+                        let thread_count = threads.unwrap_or_else(num_cpus::get);
+
+                        tokio::task::spawn_blocking(move || {
+                            let result = run_digest_mining(
+                                prefix,
+                                ignore_case,
+                                stream,
+                                nonce,
+                                tx_bytes_base64,
+                                thread_count,
+                                nonce_offset,
+                                cancel_clone,
+                                out_tx_clone.clone(),
+                            );
+
+                            if let Err(e) = result {
+                                eprintln!("Digest mining error: {}", e);
+                                let _ = out_tx_clone.blocking_send(ServerMessage::Error {
+                                    message: e.to_string(),
+                                });
+                            }
+                        });
+                    }
+                    Ok(ClientMessage::StartSplitKeyMining {
+                        target,
+                        public_key,
+                        scheme,
+                        threads,
+                    }) => {
+                        cancel.store(false, Ordering::SeqCst);
+                        let cancel_clone = cancel.clone();
+                        let out_tx_clone = out_tx.clone();
+                        let thread_count = threads.unwrap_or_else(num_cpus::get);
+
+                        tokio::task::spawn_blocking(move || {
+                            let result = run_split_key_mining(
+                                target,
+                                public_key,
+                                scheme,
+                                thread_count,
+                                cancel_clone,
+                                out_tx_clone.clone(),
+                            );
+
+                            if let Err(e) = result {
+                                eprintln!("Split-key mining error: {}", e);
+                                let _ = out_tx_clone.blocking_send(ServerMessage::Error {
+                                    message: e.to_string(),
+                                });
                             }
                         });
                     }
@@ -390,81 +804,369 @@ async fn handle_connection(
     println!("📴 Connection closed: {}", peer);
 }
 
+// =============================================================================
+// SCORE MINING (BEST-SO-FAR)
+// =============================================================================
+
+fn run_best_mining<M: MiningMode>(
+    mode_name: &str,
+    mode: M,
+    config: MinerConfig,
+    search: BestSearch,
+    gpu: bool,
+    cancel: Arc<AtomicBool>,
+    out_tx: mpsc::Sender<ServerMessage>,
+) -> Result<()> {
+    if gpu {
+        anyhow::bail!("Score mining is only supported on CPU");
+    }
+    check_scorable(&mode)?;
+
+    println!("   🏆 Starting score mining: {}", search);
+
+    let _ = out_tx.blocking_send(ServerMessage::MiningStarted {
+        mode: mode_name.to_string(),
+        prefix: String::new(),
+        pattern: search.to_string(),
+        difficulty: 0,
+        estimated_attempts: search.budget.max_attempts.unwrap_or(0),
+        threads: config.threads,
+        nonce_field: Some(config.nonce_strategy.label().to_string()),
+    });
+
+    let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let stop_progress = Arc::new(AtomicBool::new(false));
+
+    let progress_thread = thread::spawn({
+        let total_attempts = total_attempts.clone();
+        let stop_progress = stop_progress.clone();
+        let out_tx_progress = out_tx.clone();
+        move || {
+            let mut last_attempts = 0u64;
+            let mut last_time = std::time::Instant::now();
+            while !stop_progress.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(500));
+                let current = total_attempts.load(Ordering::Relaxed);
+                let now = std::time::Instant::now();
+                let elapsed = now.duration_since(last_time).as_secs_f64();
+                let hashrate = if elapsed > 0.0 {
+                    (current - last_attempts) as f64 / elapsed
+                } else {
+                    0.0
+                };
+
+                let _ = out_tx_progress.blocking_send(ServerMessage::Progress {
+                    attempts: current,
+                    hashrate,
+                });
+
+                last_attempts = current;
+                last_time = now;
+            }
+        }
+    });
+
+    let on_improve = {
+        let out_tx = out_tx.clone();
+        move |scored: &ScoredResult| {
+            let _ = out_tx.blocking_send(ServerMessage::ScoreImproved {
+                entry: ScoredEntry::from(scored),
+                attempts: scored.result.attempts,
+            });
+        }
+    };
+
+    let executor = CpuExecutor::new();
+    let results = executor.mine_best(
+        mode,
+        &config,
+        &search,
+        total_attempts.clone(),
+        cancel.clone(),
+        on_improve,
+    );
+
+    stop_progress.store(true, Ordering::SeqCst);
+    let _ = progress_thread.join();
+
+    let _ = out_tx.blocking_send(ServerMessage::BestResults {
+        results: results.iter().map(ScoredEntry::from).collect(),
+        attempts: total_attempts.load(Ordering::Relaxed),
+    });
+
+    Ok(())
+}
+
+// =============================================================================
+// STREAMING (EVERY MATCH)
+// =============================================================================
+
+/// Mine on the CPU and pass every match to `on_match`, until `count` or cancel
+fn stream_matches<M, F>(
+    mode: M,
+    config: &MinerConfig,
+    target: &TargetChecker,
+    count: Option<u64>,
+    total_attempts: &Arc<std::sync::atomic::AtomicU64>,
+    cancel: &Arc<AtomicBool>,
+    on_match: F,
+) -> u64
+where
+    M: MiningMode,
+    F: Fn(&MiningResult) + Send + Sync + 'static,
+{
+    let found = CpuExecutor::new().mine_all(
+        mode,
+        config,
+        target,
+        count,
+        total_attempts.clone(),
+        cancel.clone(),
+        on_match,
+    );
+    // Reaching the count ends the run like a cancel; only an exhausted window doesn't
+    if count.is_some_and(|count| found >= count) {
+        cancel.store(true, Ordering::SeqCst);
+    }
+    found
+}
+
+/// Send each match to the client as its own found event
+fn send_found<F>(
+    out_tx: mpsc::Sender<ServerMessage>,
+    found_message: F,
+) -> impl Fn(&MiningResult) + Send + Sync + 'static
+where
+    F: Fn(&MiningResult) -> ServerMessage + Send + Sync + 'static,
+{
+    move |res: &MiningResult| {
+        let _ = out_tx.blocking_send(found_message(res));
+    }
+}
+
 // =============================================================================
 // PACKAGE MINING
 // =============================================================================
 
 fn run_package_mining(
-    prefix: String,
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
+    objects_spec: ObjectsSpec,
+    dependency_spec: DependencySpec,
     modules: Vec<Vec<u8>>,
-    sender: String,
-    gas_budget: u64,
-    gas_price: u64,
-    gas_object_id: String,
-    gas_object_version: u64,
-    gas_object_digest: String,
+    gas: GasSpec,
     threads: usize,
-    mut start_nonce: u64,
+    start_nonce: u64,
     gpu: bool,
     cancel: Arc<AtomicBool>,
     out_tx: mpsc::Sender<ServerMessage>,
 ) -> Result<()> {
-    // If start_nonce is 0 (fresh start), randomize it to avoid re-mining the same range.
-    // Range: [100,000, u64::MAX - 8_446_744_073_709_551_615]
-    // 100,000 is safe buffer above current mainnet epoch.
-    // u64::MAX buffer avoids immediate overflow during crunching.
-    if start_nonce == 0 {
-        let mut rng = OsRng;
-        start_nonce = rng.gen_range(100_000..(u64::MAX - 8_446_744_073_709_551_615));
-        println!(
-            "Mining starting with randomized expiration epoch: {}",
-            format_large_number(start_nonce)
-        );
-    }
+    let nonce = nonce_spec.build()?;
 
     // Randomize gas budget using shared logic
-    let (effective_gas_budget, extra_gas) = randomize_gas_budget(gas_budget);
+    let (effective_gas_budget, extra_gas) = randomize_gas_budget(gas.gas_budget, nonce);
     if extra_gas > 0 {
         println!(
             "Adjusted Gas Budget: {} (Base: {} + Random: {})",
-            effective_gas_budget, gas_budget, extra_gas
+            effective_gas_budget, gas.gas_budget, extra_gas
         );
     }
 
-    use std::str::FromStr;
-
-    let target = TargetChecker::from_hex_prefix(&prefix).context("Invalid prefix")?;
-    let sender_addr = SuiAddress::from_str(&sender).context("Invalid sender")?;
+    let (sender_addr, gas_payment) = gas.build()?;
 
-    let gas_obj_id = ObjectID::from_str(&gas_object_id).context("Invalid gas object ID")?;
-    let gas_seq = SequenceNumber::from_u64(gas_object_version);
-
-    let digest_bytes = bs58::decode(&gas_object_digest)
-        .into_vec()
-        .context("Invalid gas object digest (expected Base58)")?;
-    let mut digest_arr = [0u8; 32];
-    if digest_bytes.len() != 32 {
-        anyhow::bail!("Gas object digest must be 32 bytes");
+    // Without an RPC to dry-run the publish, the UpgradeCap's index after `init`'s objects
+    // can't be found here
+    if objects_spec.guesses_cap_index() && defines_init(&modules)? {
+        anyhow::bail!(
+            "The package defines init, which may create objects ahead of the UpgradeCap; \
+             set cap_index (find it with `package --list-objects`)"
+        );
     }
-    digest_arr.copy_from_slice(&digest_bytes);
-    let gas_digest = ObjectDigest::new(digest_arr);
-
-    let gas_payment = (gas_obj_id, gas_seq, gas_digest);
 
+    let dependencies = dependency_spec.build(&modules, &[])?;
     let (tx_template, salt_offset) = create_tx_template(
         sender_addr,
         modules,
+        dependencies,
         effective_gas_budget,
-        gas_price,
+        gas.gas_price,
         gas_payment,
+        nonce,
     )?;
 
-    let _ = out_tx.blocking_send(ServerMessage::MiningStarted {
-        mode: "PACKAGE".to_string(),
-        prefix: prefix.clone(),
-        difficulty: target.difficulty(),
-        estimated_attempts: target.estimated_attempts(),
-        threads,
+    let config = MinerConfig::new(tx_template, salt_offset, threads)
+        .with_start_nonce(start_nonce)
+        .with_nonce_strategy(nonce);
+    mine_package_template(
+        "PACKAGE",
+        config,
+        target_spec,
+        score_spec,
+        stream_spec,
+        objects_spec,
+        gpu,
+        cancel,
+        out_tx,
+    )
+}
+
+fn run_upgrade_mining(
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
+    modules: Vec<Vec<u8>>,
+    package_id: String,
+    dependency_spec: DependencySpec,
+    upgrade_policy: u8,
+    upgrade_cap: UpgradeCapSpec,
+    gas: GasSpec,
+    threads: usize,
+    start_nonce: u64,
+    gpu: bool,
+    cancel: Arc<AtomicBool>,
+    out_tx: mpsc::Sender<ServerMessage>,
+) -> Result<()> {
+    let nonce = nonce_spec.build()?;
+    let (effective_gas_budget, extra_gas) = randomize_gas_budget(gas.gas_budget, nonce);
+    if extra_gas > 0 {
+        println!(
+            "Adjusted Gas Budget: {} (Base: {} + Random: {})",
+            effective_gas_budget, gas.gas_budget, extra_gas
+        );
+    }
+
+    use std::str::FromStr;
+
+    let (sender_addr, gas_payment) = gas.build()?;
+    let package_id = ObjectID::from_str(&package_id).context("Invalid package ID")?;
+    let dependencies = dependency_spec.build(&modules, &[])?;
+    let upgrade_cap = upgrade_cap.build()?;
+
+    let (tx_template, salt_offset) = create_upgrade_tx_template(
+        sender_addr,
+        package_id,
+        upgrade_cap,
+        upgrade_policy,
+        modules,
+        dependencies,
+        effective_gas_budget,
+        gas.gas_price,
+        gas_payment,
+        nonce,
+    )?;
+
+    // The new package version is created first, exactly like a publish
+    let config = MinerConfig::new(tx_template, salt_offset, threads)
+        .with_start_nonce(start_nonce)
+        .with_nonce_strategy(nonce);
+    mine_package_template(
+        "UPGRADE",
+        config,
+        target_spec,
+        score_spec,
+        stream_spec,
+        ObjectsSpec::default(),
+        gpu,
+        cancel,
+        out_tx,
+    )
+}
+
+/// Parse an object reference sent as ID, version and Base58 digest
+fn parse_object_ref(
+    object_id: &str,
+    version: u64,
+    digest: &str,
+    what: &str,
+) -> Result<(ObjectID, SequenceNumber, ObjectDigest)> {
+    use std::str::FromStr;
+
+    let object_id =
+        ObjectID::from_str(object_id).with_context(|| format!("Invalid {} ID", what))?;
+    let digest_bytes = bs58::decode(digest)
+        .into_vec()
+        .with_context(|| format!("Invalid {} digest (expected Base58)", what))?;
+    let digest: [u8; 32] = digest_bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("{} digest must be 32 bytes", what))?;
+    Ok((
+        object_id,
+        SequenceNumber::from_u64(version),
+        ObjectDigest::new(digest),
+    ))
+}
+
+/// Mine a publish or upgrade template, where the new package is the first created object
+fn mine_package_template(
+    mode_name: &str,
+    mut config: MinerConfig,
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    objects_spec: ObjectsSpec,
+    gpu: bool,
+    cancel: Arc<AtomicBool>,
+    out_tx: mpsc::Sender<ServerMessage>,
+) -> Result<()> {
+    let nonce = config.nonce_strategy;
+    // A start nonce of 0 is a fresh start, anything else resumes a previous run
+    if config.start_nonce == 0 {
+        config.start_nonce = random_start_nonce(nonce);
+        println!(
+            "Mining starting with start nonce: {}",
+            format_large_number(config.start_nonce)
+        );
+    }
+    let start_nonce = config.start_nonce;
+
+    let extra_targets = objects_spec
+        .build()
+        .context("Invalid created-object targets")?;
+    let objects_mode = (objects_spec.object_index != 0 || !extra_targets.is_empty())
+        .then(|| CreatedObjectsMode::new(objects_spec.object_index, extra_targets.clone()));
+    let goal = MiningGoal::build(&target_spec, &score_spec)
+        .and_then(|goal| goal.streaming(&stream_spec))
+        .context("Invalid target")?;
+    let (target, stream_count) = match goal {
+        MiningGoal::Target(target) => (target, None),
+        MiningGoal::All { .. } if gpu => {
+            anyhow::bail!("Streaming every match is only supported on CPU")
+        }
+        MiningGoal::All { target, count } => (target, Some(count)),
+        MiningGoal::Best(_) if !extra_targets.is_empty() => {
+            anyhow::bail!("score cannot be combined with extra object or UpgradeCap targets");
+        }
+        MiningGoal::Best(search) => {
+            return match objects_mode {
+                Some(mode) => run_best_mining(mode_name, mode, config, search, gpu, cancel, out_tx),
+                None => {
+                    run_best_mining(mode_name, PackageMode, config, search, gpu, cancel, out_tx)
+                }
+            };
+        }
+    };
+
+    let estimated_attempts = match &objects_mode {
+        Some(mode) => mode
+            .estimate(&target)
+            .map_or(target.estimated_attempts(), |e| e.mean_attempts() as u64),
+        None => target.estimated_attempts(),
+    };
+    for extra in &extra_targets {
+        println!("   🧷 Object {} target: {}", extra.index, extra.target);
+    }
+
+    let _ = out_tx.blocking_send(ServerMessage::MiningStarted {
+        mode: mode_name.to_string(),
+        prefix: target_spec.prefix.clone(),
+        pattern: target.to_string(),
+        difficulty: target.difficulty(),
+        estimated_attempts,
+        threads: config.threads,
+        nonce_field: Some(nonce.label().to_string()),
     });
 
     let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
@@ -499,136 +1201,199 @@ fn run_package_mining(
         }
     });
 
-    let mode = PackageMode;
-    let config = MinerConfig::new(tx_template, salt_offset, threads).with_start_nonce(start_nonce);
+    let found_message = {
+        let target = target.clone();
+        move |res: &MiningResult| ServerMessage::PackageFound {
+            package_id: format!("0x{}", hex::encode(res.object_id.as_ref())),
+            matched_pattern: target.pattern_label(res.pattern_index),
+            matches: res
+                .matches
+                .iter()
+                .map(|m| {
+                    let extra = extra_targets.iter().find(|e| e.index == m.object_index);
+                    MatchedObject::new(m, extra.map_or(&target, |e| &e.target))
+                })
+                .collect(),
+            tx_digest: res.tx_digest.to_string(),
+            tx_bytes_base64: general_purpose::STANDARD.encode(&res.tx_bytes),
+            attempts: res.attempts,
+            nonce_value: res.nonce_value.into(),
+        }
+    };
 
-    let result = if gpu {
-        #[cfg(feature = "gpu")]
-        {
-            let executor = crate::mining::GpuExecutor::new();
-            executor.mine(
+    let result = match (stream_count, objects_mode) {
+        (Some(count), Some(mode)) => {
+            stream_matches(
                 mode,
                 &config,
                 &target,
-                total_attempts.clone(),
-                cancel.clone(),
-            )?
+                count,
+                &total_attempts,
+                &cancel,
+                send_found(out_tx.clone(), found_message.clone()),
+            );
+            Ok(None)
         }
-        #[cfg(not(feature = "gpu"))]
-        {
-            anyhow::bail!("GPU feature is not enabled. Compile with --features gpu");
+        (Some(count), None) => {
+            stream_matches(
+                PackageMode,
+                &config,
+                &target,
+                count,
+                &total_attempts,
+                &cancel,
+                send_found(out_tx.clone(), found_message.clone()),
+            );
+            Ok(None)
         }
-    } else {
-        let executor = CpuExecutor::new();
-        executor.mine(
-            mode,
-            &config,
-            &target,
-            total_attempts.clone(),
-            cancel.clone(),
-        )
+        (None, Some(mode)) => mine_package(mode, &config, &target, gpu, &total_attempts, &cancel),
+        (None, None) => mine_package(PackageMode, &config, &target, gpu, &total_attempts, &cancel),
     };
 
+    // Without a cancel or reached count, the executor only stops on an exhausted window
+    let exhausted = nonce.max().is_some() && !cancel.load(Ordering::SeqCst);
     cancel.store(true, Ordering::SeqCst);
     let _ = progress_thread.join();
 
-    if let Some(res) = result {
-        let _ = out_tx.blocking_send(ServerMessage::PackageFound {
-            package_id: format!("0x{}", hex::encode(res.object_id.as_ref())),
-            tx_digest: res.tx_digest.to_string(),
-            tx_bytes_base64: general_purpose::STANDARD.encode(&res.tx_bytes),
-            attempts: res.attempts,
-            gas_budget_used: res.gas_budget_used,
-        });
+    if let Some(res) = result? {
+        let _ = out_tx.blocking_send(found_message(&res));
     } else {
         // Return last nonce so FE can resume
-        let last_nonce = total_attempts.load(Ordering::Relaxed);
+        let attempts = total_attempts.load(Ordering::Relaxed);
         let _ = out_tx.blocking_send(ServerMessage::Stopped {
-            attempts: last_nonce,
-            last_nonce,
+            attempts,
+            last_nonce: start_nonce + attempts,
+            exhausted,
         });
     }
 
     Ok(())
 }
 
+/// Run package mining on the GPU or CPU with either package mode
+fn mine_package<M: MiningMode>(
+    mode: M,
+    config: &MinerConfig,
+    target: &TargetChecker,
+    gpu: bool,
+    total_attempts: &Arc<std::sync::atomic::AtomicU64>,
+    cancel: &Arc<AtomicBool>,
+) -> Result<Option<MiningResult>> {
+    if gpu {
+        #[cfg(feature = "gpu")]
+        {
+            let executor = crate::mining::GpuExecutor::new();
+            executor.mine(mode, config, target, total_attempts.clone(), cancel.clone())
+        }
+        #[cfg(not(feature = "gpu"))]
+        {
+            anyhow::bail!("GPU feature is not enabled. Compile with --features gpu");
+        }
+    } else {
+        let executor = CpuExecutor::new();
+        Ok(executor.mine(mode, config, target, total_attempts.clone(), cancel.clone()))
+    }
+}
+
 // =============================================================================
 // GAS COIN MINING
 // =============================================================================
 
 fn run_gas_coin_mining(
-    prefix: String,
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
     split_amounts: Vec<u64>,
-    sender: String,
-    gas_budget: u64,
-    gas_price: u64,
-    gas_object_id: String,
-    gas_object_version: u64,
-    gas_object_digest: String,
+    min_matches: u16,
+    gas: GasSpec,
     threads: usize,
     mut start_nonce: u64,
     gpu: bool,
     cancel: Arc<AtomicBool>,
     out_tx: mpsc::Sender<ServerMessage>,
 ) -> Result<()> {
-    // If start_nonce is 0, randomize it
+    let nonce = nonce_spec.build()?;
+    if min_matches as usize > split_amounts.len() {
+        anyhow::bail!(
+            "min_matches {} is more than the {} split coins",
+            min_matches,
+            split_amounts.len()
+        );
+    }
+
+    // A start nonce of 0 is a fresh start, anything else resumes a previous run
     if start_nonce == 0 {
-        let mut rng = OsRng;
-        start_nonce = rng.gen_range(100_000..(u64::MAX - 8_446_744_073_709_551_615));
+        start_nonce = random_start_nonce(nonce);
         println!(
-            "Gas coin mining starting with randomized expiration epoch: {}",
+            "Gas coin mining starting with start nonce: {}",
             format_large_number(start_nonce)
         );
     }
 
     // Randomize gas budget
-    let (effective_gas_budget, extra_gas) = randomize_gas_budget(gas_budget);
+    let (effective_gas_budget, extra_gas) = randomize_gas_budget(gas.gas_budget, nonce);
     if extra_gas > 0 {
         println!(
             "Adjusted Gas Budget: {} (Base: {} + Random: {})",
-            effective_gas_budget, gas_budget, extra_gas
+            effective_gas_budget, gas.gas_budget, extra_gas
         );
     }
 
-    use std::str::FromStr;
-
-    let target = TargetChecker::from_hex_prefix(&prefix).context("Invalid prefix")?;
-    let sender_addr = SuiAddress::from_str(&sender).context("Invalid sender")?;
-
-    let gas_obj_id = ObjectID::from_str(&gas_object_id).context("Invalid gas object ID")?;
-    let gas_seq = SequenceNumber::from_u64(gas_object_version);
-
-    let digest_bytes = bs58::decode(&gas_object_digest)
-        .into_vec()
-        .context("Invalid gas object digest (expected Base58)")?;
-    let mut digest_arr = [0u8; 32];
-    if digest_bytes.len() != 32 {
-        anyhow::bail!("Gas object digest must be 32 bytes");
-    }
-    digest_arr.copy_from_slice(&digest_bytes);
-    let gas_digest = ObjectDigest::new(digest_arr);
-
-    let gas_payment = (gas_obj_id, gas_seq, gas_digest);
+    let (sender_addr, gas_payment) = gas.build()?;
 
     let (tx_template, salt_offset, num_outputs) = create_split_tx_template(
         sender_addr,
         split_amounts.clone(),
         effective_gas_budget,
-        gas_price,
+        gas.gas_price,
         gas_payment,
+        nonce,
     )?;
 
+    let goal = MiningGoal::build(&target_spec, &score_spec)
+        .and_then(|goal| goal.streaming(&stream_spec))
+        .context("Invalid target")?;
+    let (target, stream_count) = match goal {
+        MiningGoal::Target(target) => (target, None),
+        MiningGoal::All { .. } if gpu => {
+            anyhow::bail!("Streaming every match is only supported on CPU")
+        }
+        MiningGoal::All { target, count } => (target, Some(count)),
+        MiningGoal::Best(search) => {
+            let config = MinerConfig::new(tx_template, salt_offset, threads)
+                .with_start_nonce(start_nonce)
+                .with_nonce_strategy(nonce);
+            return run_best_mining(
+                "GAS_COIN",
+                GasCoinMode::new(num_outputs).with_min_matches(min_matches),
+                config,
+                search,
+                gpu,
+                cancel,
+                out_tx,
+            );
+        }
+    };
+
     println!(
-        "🪙 Gas Coin mining: prefix=0x{}, split_amounts={:?}, outputs={}",
-        prefix, split_amounts, num_outputs
+        "🪙 Gas Coin mining: target={}, split_amounts={:?}, outputs={}",
+        target, split_amounts, num_outputs
     );
 
+    let mode = GasCoinMode::new(num_outputs).with_min_matches(min_matches);
+    let estimated_attempts = mode
+        .estimate(&target)
+        .map_or(target.estimated_attempts(), |e| e.mean_attempts() as u64);
+
     let _ = out_tx.blocking_send(ServerMessage::MiningStarted {
         mode: "GAS_COIN".to_string(),
-        prefix: prefix.clone(),
+        prefix: target_spec.prefix.clone(),
+        pattern: target.to_string(),
         difficulty: target.difficulty(),
-        estimated_attempts: target.estimated_attempts(),
+        estimated_attempts,
         threads,
+        nonce_field: Some(nonce.label().to_string()),
     });
 
     let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
@@ -663,10 +1428,40 @@ fn run_gas_coin_mining(
         }
     });
 
-    let mode = GasCoinMode::new(num_outputs);
-    let config = MinerConfig::new(tx_template, salt_offset, threads).with_start_nonce(start_nonce);
+    let config = MinerConfig::new(tx_template, salt_offset, threads)
+        .with_start_nonce(start_nonce)
+        .with_nonce_strategy(nonce);
 
-    let result = if gpu {
+    let found_message = {
+        let target = target.clone();
+        move |res: &MiningResult| ServerMessage::GasCoinFound {
+            object_id: format!("0x{}", hex::encode(res.object_id.as_ref())),
+            object_index: res.object_index,
+            matched_pattern: target.pattern_label(res.pattern_index),
+            matches: res
+                .matches
+                .iter()
+                .map(|m| MatchedObject::new(m, &target))
+                .collect(),
+            tx_digest: res.tx_digest.to_string(),
+            tx_bytes_base64: general_purpose::STANDARD.encode(&res.tx_bytes),
+            attempts: res.attempts,
+            nonce_value: res.nonce_value.into(),
+        }
+    };
+
+    let result = if let Some(count) = stream_count {
+        stream_matches(
+            mode,
+            &config,
+            &target,
+            count,
+            &total_attempts,
+            &cancel,
+            send_found(out_tx.clone(), found_message.clone()),
+        );
+        None
+    } else if gpu {
         #[cfg(feature = "gpu")]
         {
             let executor = crate::mining::GpuExecutor::new();
@@ -693,23 +1488,19 @@ fn run_gas_coin_mining(
         )
     };
 
+    // Without a cancel or reached count, the executor only stops on an exhausted window
+    let exhausted = nonce.max().is_some() && !cancel.load(Ordering::SeqCst);
     cancel.store(true, Ordering::SeqCst);
     let _ = progress_thread.join();
 
     if let Some(res) = result {
-        let _ = out_tx.blocking_send(ServerMessage::GasCoinFound {
-            object_id: format!("0x{}", hex::encode(res.object_id.as_ref())),
-            object_index: res.object_index,
-            tx_digest: res.tx_digest.to_string(),
-            tx_bytes_base64: general_purpose::STANDARD.encode(&res.tx_bytes),
-            attempts: res.attempts,
-            gas_budget_used: res.gas_budget_used,
-        });
+        let _ = out_tx.blocking_send(found_message(&res));
     } else {
-        let last_nonce = total_attempts.load(Ordering::Relaxed);
+        let attempts = total_attempts.load(Ordering::Relaxed);
         let _ = out_tx.blocking_send(ServerMessage::Stopped {
-            attempts: last_nonce,
-            last_nonce,
+            attempts,
+            last_nonce: start_nonce + attempts,
+            exhausted,
         });
     }
 
@@ -721,9 +1512,60 @@ fn run_gas_coin_mining(
 // =============================================================================
 
 fn run_move_call_mining(
-    prefix: String,
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
+    tx_bytes_base64: String,
+    index_spec: IndexSpec,
+    threads: usize,
+    start_nonce: u64,
+    gpu: bool,
+    cancel: Arc<AtomicBool>,
+    out_tx: mpsc::Sender<ServerMessage>,
+) -> Result<()> {
+    let nonce = nonce_spec.build()?;
+    let indices = index_spec.build()?;
+    println!("   🔢 Move Call object index: {}", indices);
+
+    match indices {
+        ObjectIndices::Single(index) => mine_move_call(
+            SingleObjectMode::new(index),
+            target_spec,
+            score_spec,
+            stream_spec,
+            nonce,
+            tx_bytes_base64,
+            threads,
+            start_nonce,
+            gpu,
+            cancel,
+            out_tx,
+        ),
+        ObjectIndices::Range(start, end) => mine_move_call(
+            ObjectRangeMode::new(start, end),
+            target_spec,
+            score_spec,
+            stream_spec,
+            nonce,
+            tx_bytes_base64,
+            threads,
+            start_nonce,
+            gpu,
+            cancel,
+            out_tx,
+        ),
+    }
+}
+
+/// Mine a Move call template with a single-index or index-range mode
+fn mine_move_call<M: MiningMode>(
+    mode: M,
+    target_spec: TargetSpec,
+    score_spec: ScoreSpec,
+    stream_spec: StreamSpec,
+    nonce: NonceStrategy,
     tx_bytes_base64: String,
-    object_index: u16,
     threads: usize,
     start_nonce: u64,
     gpu: bool,
@@ -737,27 +1579,47 @@ fn run_move_call_mining(
 
     // 1. Create Transaction Template from generic bytes
     // This allows mining ANY transaction (Move Calls, etc.)
-    let (tx_template, salt_offset) = create_template_from_bytes(&tx_bytes)
+    let (tx_template, salt_offset) = create_template_from_bytes(&tx_bytes, nonce)
         .context("Failed to create mining template from transaction bytes")?;
 
-    let target = TargetChecker::from_hex_prefix(&prefix).context("Invalid prefix")?;
+    let goal = MiningGoal::build(&target_spec, &score_spec)
+        .and_then(|goal| goal.streaming(&stream_spec))
+        .context("Invalid target")?;
+    let (target, stream_count) = match goal {
+        MiningGoal::Target(target) => (target, None),
+        MiningGoal::All { .. } if gpu => {
+            anyhow::bail!("Streaming every match is only supported on CPU")
+        }
+        MiningGoal::All { target, count } => (target, Some(count)),
+        MiningGoal::Best(search) => {
+            let config = MinerConfig::new(tx_template, salt_offset, threads)
+                .with_start_nonce(start_nonce)
+                .with_nonce_strategy(nonce);
+            return run_best_mining("MoveCall", mode, config, search, gpu, cancel, out_tx);
+        }
+    };
 
     println!("   🚀 Starting Move Call mining...");
-    println!("      Prefix: 0x{}", prefix);
+    println!("      Target: {}", target);
     println!("      Threads: {}", threads);
-    println!("      Target Index: {}", object_index);
+    let (index_start, index_end) = mode.index_range();
+    println!("      Target Indices: {}..{}", index_start, index_end);
     println!("      Start Nonce: {}", format_large_number(start_nonce));
     println!("      Template size: {} bytes", tx_template.len());
 
     let _ = out_tx.blocking_send(ServerMessage::MiningStarted {
         mode: "MoveCall".to_string(),
-        prefix: prefix.clone(),
+        prefix: target_spec.prefix.clone(),
+        pattern: target.to_string(),
         difficulty: target.difficulty(),
-        estimated_attempts: target.estimated_attempts(),
+        estimated_attempts: mode
+            .estimate(&target)
+            .map_or(target.estimated_attempts(), |e| e.mean_attempts() as u64),
         threads,
+        nonce_field: Some(nonce.label().to_string()),
     });
 
-    let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(start_nonce));
+    let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
 
     // Progress Reporter
     let progress_thread = thread::spawn({
@@ -765,7 +1627,7 @@ fn run_move_call_mining(
         let cancel = cancel.clone();
         let out_tx_progress = out_tx.clone();
         move || {
-            let mut last_attempts = start_nonce;
+            let mut last_attempts = 0u64;
             let mut last_time = std::time::Instant::now();
             while !cancel.load(Ordering::Relaxed) {
                 thread::sleep(Duration::from_millis(500));
@@ -793,11 +1655,36 @@ fn run_move_call_mining(
         }
     });
 
-    // 2. Start Mining using Generic SingleObjectMode
-    let mode = SingleObjectMode::new(object_index); // Check specific index (e.g. 0)
-    let config = MinerConfig::new(tx_template, salt_offset, threads).with_start_nonce(start_nonce);
+    // 2. Start Mining with the requested index mode
+    let config = MinerConfig::new(tx_template, salt_offset, threads)
+        .with_start_nonce(start_nonce)
+        .with_nonce_strategy(nonce);
 
-    let result = if gpu {
+    let found_message = {
+        let target = target.clone();
+        move |res: &MiningResult| ServerMessage::MoveCallFound {
+            object_id: format!("0x{}", hex::encode(res.object_id.as_ref())),
+            object_index: res.object_index,
+            matched_pattern: target.pattern_label(res.pattern_index),
+            tx_digest: res.tx_digest.to_string(),
+            tx_bytes_base64: general_purpose::STANDARD.encode(&res.tx_bytes),
+            attempts: res.attempts,
+            nonce_value: res.nonce_value.into(),
+        }
+    };
+
+    let result = if let Some(count) = stream_count {
+        stream_matches(
+            mode,
+            &config,
+            &target,
+            count,
+            &total_attempts,
+            &cancel,
+            send_found(out_tx.clone(), found_message.clone()),
+        );
+        None
+    } else if gpu {
         #[cfg(feature = "gpu")]
         {
             let executor = crate::mining::GpuExecutor::new();
@@ -824,23 +1711,240 @@ fn run_move_call_mining(
         )
     };
 
+    // Without a cancel or reached count, the executor only stops on an exhausted window
+    let exhausted = nonce.max().is_some() && !cancel.load(Ordering::SeqCst);
     cancel.store(true, Ordering::SeqCst);
     let _ = progress_thread.join();
 
     if let Some(res) = result {
-        let _ = out_tx.blocking_send(ServerMessage::MoveCallFound {
-            object_id: format!("0x{}", hex::encode(res.object_id.as_ref())),
-            object_index: res.object_index,
+        let _ = out_tx.blocking_send(found_message(&res));
+    } else {
+        let attempts = total_attempts.load(Ordering::Relaxed);
+        let _ = out_tx.blocking_send(ServerMessage::Stopped {
+            attempts,
+            last_nonce: start_nonce + attempts,
+            exhausted,
+        });
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// TRANSACTION DIGEST MINING
+// =============================================================================
+
+fn run_digest_mining(
+    prefix: String,
+    ignore_case: bool,
+    stream_spec: StreamSpec,
+    nonce_spec: NonceSpec,
+    tx_bytes_base64: String,
+    threads: usize,
+    start_nonce: u64,
+    cancel: Arc<AtomicBool>,
+    out_tx: mpsc::Sender<ServerMessage>,
+) -> Result<()> {
+    let nonce = nonce_spec.build()?;
+    let tx_bytes = general_purpose::STANDARD
+        .decode(&tx_bytes_base64)
+        .context("Failed to decode base64 transaction bytes")?;
+    let (tx_template, salt_offset) = create_template_from_bytes(&tx_bytes, nonce)
+        .context("Failed to create mining template from transaction bytes")?;
+    let target =
+        TargetChecker::from_base58_prefix(&prefix, ignore_case).context("Invalid Base58 prefix")?;
+    let stream_count = stream_spec.is_set().then_some(stream_spec.count);
+
+    println!("   🚀 Starting transaction digest mining...");
+    println!("      Target: {}", target);
+    println!("      Threads: {}", threads);
+
+    let _ = out_tx.blocking_send(ServerMessage::MiningStarted {
+        mode: "DIGEST".to_string(),
+        prefix: prefix.clone(),
+        pattern: target.to_string(),
+        difficulty: target.difficulty(),
+        estimated_attempts: target.estimated_attempts(),
+        threads,
+        nonce_field: Some(nonce.label().to_string()),
+    });
+
+    let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let progress_thread = thread::spawn({
+        let total_attempts = total_attempts.clone();
+        let cancel = cancel.clone();
+        let out_tx_progress = out_tx.clone();
+        move || {
+            let mut last_attempts = 0u64;
+            let mut last_time = std::time::Instant::now();
+            while !cancel.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(500));
+                let current = total_attempts.load(Ordering::Relaxed);
+                let now = std::time::Instant::now();
+                let elapsed = now.duration_since(last_time).as_secs_f64();
+                let hashrate = if elapsed > 0.0 {
+                    (current - last_attempts) as f64 / elapsed
+                } else {
+                    0.0
+                };
+                let _ = out_tx_progress.blocking_send(ServerMessage::Progress {
+                    attempts: current,
+                    hashrate,
+                });
+                last_attempts = current;
+                last_time = now;
+            }
+        }
+    });
+
+    let config = MinerConfig::new(tx_template, salt_offset, threads)
+        .with_start_nonce(start_nonce)
+        .with_nonce_strategy(nonce);
+    let found_message = {
+        let target = target.clone();
+        move |res: &MiningResult| ServerMessage::DigestFound {
             tx_digest: res.tx_digest.to_string(),
+            matched_pattern: target.pattern_label(res.pattern_index),
             tx_bytes_base64: general_purpose::STANDARD.encode(&res.tx_bytes),
             attempts: res.attempts,
-            gas_budget_used: res.gas_budget_used,
+            nonce_value: res.nonce_value.into(),
+        }
+    };
+
+    let result = match stream_count {
+        Some(count) => {
+            stream_matches(
+                DigestMode,
+                &config,
+                &target,
+                count,
+                &total_attempts,
+                &cancel,
+                send_found(out_tx.clone(), found_message.clone()),
+            );
+            None
+        }
+        None => CpuExecutor::new().mine(
+            DigestMode,
+            &config,
+            &target,
+            total_attempts.clone(),
+            cancel.clone(),
+        ),
+    };
+
+    // Without a cancel or reached count, the executor only stops on an exhausted window
+    let exhausted = nonce.max().is_some() && !cancel.load(Ordering::SeqCst);
+    cancel.store(true, Ordering::SeqCst);
+    let _ = progress_thread.join();
+
+    if let Some(res) = result {
+        let _ = out_tx.blocking_send(found_message(&res));
+    } else {
+        let attempts = total_attempts.load(Ordering::Relaxed);
+        let _ = out_tx.blocking_send(ServerMessage::Stopped {
+            attempts,
+            last_nonce: start_nonce + attempts,
+            exhausted,
+        });
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// SPLIT-KEY MINING (VANITY ADDRESS)
+// =============================================================================
+
+fn run_split_key_mining(
+    target_spec: TargetSpec,
+    public_key: String,
+    scheme: KeyScheme,
+    threads: usize,
+    cancel: Arc<AtomicBool>,
+    out_tx: mpsc::Sender<ServerMessage>,
+) -> Result<()> {
+    let target = target_spec.build().context("Invalid target")?;
+    let public_key = split::parse_public_key(scheme, &public_key)?;
+    let mode = SplitKeyMode::new(scheme, public_key)?;
+
+    println!("   🧩 Starting split-key mining...");
+    println!("      Target: {}", target);
+    println!("      Scheme: {}", scheme);
+    println!("      Threads: {}", threads);
+
+    let _ = out_tx.blocking_send(ServerMessage::MiningStarted {
+        mode: "SplitKey".to_string(),
+        prefix: target_spec.prefix.clone(),
+        pattern: target.to_string(),
+        difficulty: target.difficulty(),
+        estimated_attempts: target.estimated_attempts(),
+        threads,
+        nonce_field: None,
+    });
+
+    let total_attempts = Arc::new(std::sync::atomic::AtomicU64::new(0));
+
+    // Progress Reporter
+    let progress_thread = thread::spawn({
+        let total_attempts = total_attempts.clone();
+        let cancel = cancel.clone();
+        let out_tx_progress = out_tx.clone();
+        move || {
+            let mut last_attempts = 0;
+            let mut last_time = std::time::Instant::now();
+            while !cancel.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(500));
+                let current = total_attempts.load(Ordering::Relaxed);
+
+                if current > last_attempts {
+                    let now = std::time::Instant::now();
+                    let elapsed = now.duration_since(last_time).as_secs_f64();
+                    let hashrate = if elapsed > 0.0 {
+                        (current - last_attempts) as f64 / elapsed
+                    } else {
+                        0.0
+                    };
+
+                    let _ = out_tx_progress.blocking_send(ServerMessage::Progress {
+                        attempts: current,
+                        hashrate,
+                    });
+
+                    last_attempts = current;
+                    last_time = now;
+                }
+            }
+        }
+    });
+
+    let result = search(
+        mode,
+        threads,
+        &target,
+        total_attempts.clone(),
+        cancel.clone(),
+    );
+
+    cancel.store(true, Ordering::SeqCst);
+    let _ = progress_thread.join();
+
+    if let Some(res) = result {
+        let _ = out_tx.blocking_send(ServerMessage::SplitKeyFound {
+            address: format!("0x{}", hex::encode(res.found.address)),
+            offset: hex::encode(res.found.offset),
+            scheme: scheme.to_string(),
+            matched_pattern: target
+                .pattern_label(target.find_match(&res.found.address).unwrap_or_default()),
+            attempts: res.attempts,
         });
     } else {
-        let last_nonce = total_attempts.load(Ordering::Relaxed);
+        // Offsets are random per thread, so there is no nonce to resume from
+        let attempts = total_attempts.load(Ordering::Relaxed);
         let _ = out_tx.blocking_send(ServerMessage::Stopped {
-            attempts: last_nonce,
-            last_nonce,
+            attempts,
+            last_nonce: 0,
+            exhausted: false,
         });
     }
 
@@ -0,0 +1,189 @@
+//! Base58 prefixes - match the Base58 rendering of 32 bytes (e.g. a transaction digest)
+
+use std::fmt;
+
+/// Bitcoin alphabet, as used by Sui digests
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Longest Base58 rendering of 32 bytes
+const MAX_CHARS: usize = 44;
+
+/// A Base58 prefix, optionally case-insensitive
+#[derive(Debug, Clone)]
+pub struct Base58Prefix {
+    prefix: String,
+    case_insensitive: bool,
+    /// Accepted digit values for each prefix char
+    digits: Vec<Vec<u8>>,
+}
+
+impl Base58Prefix {
+    pub fn new(prefix: &str, case_insensitive: bool) -> anyhow::Result<Self> {
+        let prefix = prefix.trim();
+        if prefix.is_empty() {
+            anyhow::bail!("Base58 prefix must not be empty");
+        }
+        if prefix.len() > MAX_CHARS {
+            anyhow::bail!(
+                "Base58 prefix too long: {} chars (max {})",
+                prefix.len(),
+                MAX_CHARS
+            );
+        }
+
+        let mut digits = Vec::with_capacity(prefix.len());
+        for c in prefix.bytes() {
+            let accepted: Vec<u8> = (0..58u8)
+                .filter(|&d| {
+                    let a = ALPHABET[d as usize];
+                    a == c || (case_insensitive && a.eq_ignore_ascii_case(&c))
+                })
+                .collect();
+            if accepted.is_empty() {
+                anyhow::bail!(
+                    "'{}' is not a Base58 character (0, O, I and l are excluded)",
+                    c as char
+                );
+            }
+            digits.push(accepted);
+        }
+
+        Ok(Self {
+            prefix: prefix.to_string(),
+            case_insensitive,
+            digits,
+        })
+    }
+
+    #[inline(always)]
+    pub fn matches(&self, bytes: &[u8; 32]) -> bool {
+        let mut encoded = [0u8; MAX_CHARS];
+        let Ok(len) = bs58::encode(bytes).onto(&mut encoded[..]) else {
+            return false;
+        };
+        if len < self.prefix.len() {
+            return false;
+        }
+        let encoded = &encoded[..self.prefix.len()];
+        if self.case_insensitive {
+            encoded.eq_ignore_ascii_case(self.prefix.as_bytes())
+        } else {
+            encoded == self.prefix.as_bytes()
+        }
+    }
+
+    /// Probability that 32 uniformly random bytes render with this prefix
+    ///
+    /// Not simply `58^-len`: 2^256 isn't a power of 58, so 44-char renderings only start
+    /// with the first ~18 digits and some leading chars are far rarer than others.
+    pub fn probability(&self) -> f64 {
+        prefix_probability(&self.digits, 32)
+    }
+}
+
+/// Probability that `bytes` uniformly random bytes render with a prefix of the given digits
+fn prefix_probability(digits: &[Vec<u8>], bytes: i32) -> f64 {
+    if digits.is_empty() {
+        return 1.0;
+    }
+    if bytes == 0 {
+        return 0.0;
+    }
+    // Each leading '1' stands for a leading zero byte
+    if digits[0] == [0] {
+        return prefix_probability(&digits[1..], bytes - 1) / 256.0;
+    }
+
+    // Values without a leading zero byte: [2^(8·(bytes-1)), 2^(8·bytes))
+    let low = 2f64.powi(8 * (bytes - 1));
+    let high = 2f64.powi(8 * bytes);
+    let k = digits.len() as i32;
+    let rest: f64 = digits[1..].iter().map(|d| d.len() as f64).product();
+    let max_len = (8.0 * bytes as f64 / 58f64.log2()).ceil() as i32;
+
+    let mut count = 0.0;
+    for len in k..=max_len {
+        // Values whose rendering has `len` chars and starts with digit `d`
+        let span = 58f64.powi(len - 1);
+        for &d in digits[0].iter().filter(|&&d| d != 0) {
+            let start = d as f64 * span;
+            let covered = ((start + span).min(high) - start.max(low)).max(0.0) / span;
+            count += covered * rest * 58f64.powi(len - k);
+        }
+    }
+    count / high
+}
+
+impl fmt::Display for Base58Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}…", self.prefix)?;
+        if self.case_insensitive {
+            write!(f, " (any case)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random 32-byte values (splitmix64)
+    fn samples(count: usize) -> impl Iterator<Item = [u8; 32]> {
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        (0..count).map(move |_| {
+            let mut bytes = [0u8; 32];
+            for chunk in bytes.chunks_mut(8) {
+                state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                chunk.copy_from_slice(&(z ^ (z >> 31)).to_le_bytes());
+            }
+            bytes
+        })
+    }
+
+    #[test]
+    fn test_base58_prefix_match() {
+        let bytes = [7u8; 32];
+        let encoded = bs58::encode(bytes).into_string();
+
+        assert!(
+            Base58Prefix::new(&encoded[..3], false)
+                .unwrap()
+                .matches(&bytes)
+        );
+        let lower = encoded[..3].to_ascii_lowercase();
+        assert!(Base58Prefix::new(&lower, true).unwrap().matches(&bytes));
+        assert!(!Base58Prefix::new("zzz", false).unwrap().matches(&bytes));
+
+        assert!(Base58Prefix::new("0x", false).is_err());
+        assert!(Base58Prefix::new("l", false).is_err());
+        // Lowercase 'l' has an uppercase 'L' in the alphabet
+        assert!(Base58Prefix::new("l", true).is_ok());
+    }
+
+    #[test]
+    fn test_base58_probability_matches_sampling() {
+        // 2^256 / 58^43 ≈ 17.8, so a 44-char rendering starts with one of the first 18 digits
+        let common = Base58Prefix::new("A", false).unwrap();
+        let rare = Base58Prefix::new("z", false).unwrap();
+        assert!(common.probability() > 50.0 * rare.probability());
+        assert!((Base58Prefix::new("1", false).unwrap().probability() - 1.0 / 256.0).abs() < 1e-4);
+
+        for prefix in [common, Base58Prefix::new("c", true).unwrap()] {
+            let n = 200_000;
+            let hits = samples(n).filter(|b| prefix.matches(b)).count();
+            let observed = hits as f64 / n as f64;
+            let expected = prefix.probability();
+            assert!(
+                (observed - expected).abs() < expected * 0.1,
+                "{}: observed {}, expected {}",
+                prefix,
+                observed,
+                expected
+            );
+        }
+    }
+}
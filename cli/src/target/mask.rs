@@ -0,0 +1,216 @@
+use std::fmt;
+
+/// Number of hex chars (nibbles) in a Sui Object ID
+pub const ID_NIBBLES: usize = 64;
+
+/// Single hex pattern stored as a per-byte value/mask pair
+///
+/// Every nibble of the 32-byte ID is either fixed (mask nibble = 0xF) or "don't care"
+/// (mask nibble = 0x0). Prefixes, suffixes and masked patterns like `cafe??00` or
+/// `cafe*0000` all compile down to the same form.
+#[derive(Debug, Clone)]
+pub struct MaskPattern {
+    /// Expected nibble values (zero wherever the mask is zero)
+    value: [u8; 32],
+    /// 0xF0 / 0x0F / 0xFF for fixed nibbles, 0x00 for "don't care"
+    mask: [u8; 32],
+    /// Byte range [start, end) that contains every fixed nibble
+    start: usize,
+    end: usize,
+    /// Number of fixed nibbles
+    fixed_nibbles: usize,
+}
+
+impl MaskPattern {
+    /// A pattern with no fixed nibbles (matches every ID)
+    pub fn empty() -> Self {
+        Self {
+            value: [0u8; 32],
+            mask: [0u8; 32],
+            start: 0,
+            end: 0,
+            fixed_nibbles: 0,
+        }
+    }
+
+    /// Create a pattern from a hex prefix string (without "0x")
+    pub fn from_hex_prefix(hex_prefix: &str) -> Result<Self, anyhow::Error> {
+        let prefix_len = hex_prefix.len();
+
+        // Validate prefix length (max 64 hex chars = 32 bytes for a Sui Object ID)
+        if prefix_len > ID_NIBBLES {
+            anyhow::bail!("Prefix too long: {} chars (max 64)", prefix_len);
+        }
+
+        let mut checker = Self::empty();
+        for (pos, c) in hex_prefix.chars().enumerate() {
+            checker.fix_nibble(pos, c)?;
+        }
+        Ok(checker)
+    }
+
+    /// Create a pattern from a masked pattern string
+    ///
+    /// Hex chars are fixed nibbles and `?` is a "don't care" nibble, anchored at the start
+    /// of the ID (`cafe??00`). A single `*` expands to as many `?` as needed to fill the
+    /// 64-char ID, which anchors the rest of the pattern at the end (`cafe*0000`).
+    pub fn from_pattern(pattern: &str) -> Result<Self, anyhow::Error> {
+        let pattern = pattern.trim_start_matches("0x");
+
+        let expanded = match pattern.matches('*').count() {
+            0 => pattern.to_string(),
+            1 => {
+                let fixed_len = pattern.len() - 1;
+                if fixed_len > ID_NIBBLES {
+                    anyhow::bail!("Pattern too long: {} chars (max 64)", fixed_len);
+                }
+                pattern.replacen('*', &"?".repeat(ID_NIBBLES - fixed_len), 1)
+            }
+            _ => anyhow::bail!("Pattern may contain at most one '*'"),
+        };
+
+        if expanded.len() > ID_NIBBLES {
+            anyhow::bail!("Pattern too long: {} chars (max 64)", expanded.len());
+        }
+
+        let mut checker = Self::empty();
+        for (pos, c) in expanded.chars().enumerate() {
+            if c != '?' {
+                checker.fix_nibble(pos, c)?;
+            }
+        }
+
+        if checker.fixed_nibbles == 0 {
+            anyhow::bail!("Pattern must fix at least one hex char");
+        }
+        Ok(checker)
+    }
+
+    /// Additionally require the ID to end with the given hex suffix
+    pub fn with_suffix(mut self, hex_suffix: &str) -> Result<Self, anyhow::Error> {
+        let suffix_len = hex_suffix.len();
+
+        if suffix_len > ID_NIBBLES {
+            anyhow::bail!("Suffix too long: {} chars (max 64)", suffix_len);
+        }
+
+        for (i, c) in hex_suffix.chars().enumerate() {
+            let pos = ID_NIBBLES - suffix_len + i;
+            if self.is_fixed(pos) {
+                anyhow::bail!("Suffix overlaps the prefix (max 64 chars combined)");
+            }
+            self.fix_nibble(pos, c)?;
+        }
+        Ok(self)
+    }
+
+    /// Fix the nibble at `pos` (0 = high nibble of byte 0) to the given hex char
+    fn fix_nibble(&mut self, pos: usize, c: char) -> Result<(), anyhow::Error> {
+        let nibble = c
+            .to_digit(16)
+            .ok_or_else(|| anyhow::anyhow!("Invalid hex char '{}' in pattern", c))?
+            as u8;
+
+        let byte = pos / 2;
+        let (shift, nibble_mask) = if pos.is_multiple_of(2) {
+            (4, 0xF0)
+        } else {
+            (0, 0x0F)
+        };
+
+        if self.mask[byte] & nibble_mask == 0 {
+            self.fixed_nibbles += 1;
+        }
+        self.value[byte] = (self.value[byte] & !nibble_mask) | (nibble << shift);
+        self.mask[byte] |= nibble_mask;
+
+        if self.start == self.end {
+            self.start = byte;
+            self.end = byte + 1;
+        } else {
+            self.start = self.start.min(byte);
+            self.end = self.end.max(byte + 1);
+        }
+        Ok(())
+    }
+
+    pub fn is_fixed(&self, pos: usize) -> bool {
+        let nibble_mask = if pos.is_multiple_of(2) { 0xF0 } else { 0x0F };
+        self.mask[pos / 2] & nibble_mask != 0
+    }
+
+    /// Fixed nibble value at `pos`, or None for "don't care"
+    pub fn nibble(&self, pos: usize) -> Option<u8> {
+        if !self.is_fixed(pos) {
+            return None;
+        }
+        let byte = self.value[pos / 2];
+        Some(if pos.is_multiple_of(2) {
+            byte >> 4
+        } else {
+            byte & 0x0F
+        })
+    }
+
+    /// Check if the given 32-byte ID matches the pattern
+    #[inline(always)]
+    pub fn matches(&self, id_bytes: &[u8; 32]) -> bool {
+        // Accumulate differences over the fixed range without branching per byte
+        let range = self.start..self.end;
+        let diff = id_bytes[range.clone()]
+            .iter()
+            .zip(&self.value[range.clone()])
+            .zip(&self.mask[range])
+            .fold(0u8, |acc, ((id, value), mask)| acc | ((id ^ value) & mask));
+        diff == 0
+    }
+
+    /// Number of fixed hex characters
+    pub fn fixed_nibbles(&self) -> usize {
+        self.fixed_nibbles
+    }
+
+    /// Number of leading fixed nibbles
+    pub fn prefix_len(&self) -> usize {
+        (0..ID_NIBBLES)
+            .take_while(|&pos| self.is_fixed(pos))
+            .count()
+    }
+
+    /// Whether the fixed nibbles form a plain prefix
+    pub fn is_prefix_only(&self) -> bool {
+        self.prefix_len() == self.fixed_nibbles
+    }
+
+    /// Raw prefix bytes (last nibble zero-padded for odd lengths)
+    pub fn prefix_bytes(&self) -> Vec<u8> {
+        self.value[..self.prefix_len().div_ceil(2)].to_vec()
+    }
+}
+
+impl fmt::Display for MaskPattern {
+    /// Human readable pattern, e.g. `0xcafe`, `0xcafe…beef` or `0xcafe??00`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nibbles: String = (0..ID_NIBBLES)
+            .map(|pos| match self.nibble(pos) {
+                Some(nibble) => char::from_digit(nibble as u32, 16).unwrap(),
+                None => '?',
+            })
+            .collect();
+
+        let prefix_len = self.prefix_len();
+        let suffix_len = nibbles.chars().rev().take_while(|&c| c != '?').count();
+
+        if prefix_len + suffix_len == self.fixed_nibbles && suffix_len > 0 && prefix_len < 64 {
+            // Prefix and/or suffix with a free middle
+            write!(
+                f,
+                "0x{}…{}",
+                &nibbles[..prefix_len],
+                &nibbles[ID_NIBBLES - suffix_len..]
+            )
+        } else {
+            write!(f, "0x{}", nibbles.trim_end_matches('?'))
+        }
+    }
+}
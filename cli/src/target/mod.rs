@@ -0,0 +1,498 @@
+//! Target patterns - what a derived ID has to look like to count as a match
+
+mod base58;
+mod mask;
+mod regex;
+mod set;
+
+use base58::Base58Prefix;
+use mask::MaskPattern;
+use regex::RegexPattern;
+use set::PrefixSet;
+use std::fmt;
+use std::sync::Arc;
+
+/// Target checker for Package ID matching
+///
+/// Either a single hex pattern (prefix, suffix or masked pattern), a set of prefixes
+/// of which any one may match, a regex over the `0x…` hex rendering of the ID, or a
+/// Base58 prefix (for transaction digests).
+#[derive(Debug, Clone)]
+pub struct TargetChecker {
+    kind: TargetKind,
+}
+
+#[derive(Debug, Clone)]
+enum TargetKind {
+    Pattern(MaskPattern),
+    /// Shared between mining threads, the lookup table can be large
+    Set(Arc<PrefixSet>),
+    Regex(RegexPattern),
+    Base58(Base58Prefix),
+}
+
+impl TargetChecker {
+    fn from_mask(mask: MaskPattern) -> Self {
+        Self {
+            kind: TargetKind::Pattern(mask),
+        }
+    }
+
+    /// Create a new TargetChecker from hex prefix string
+    /// The prefix should be without "0x" prefix
+    pub fn from_hex_prefix(hex_prefix: &str) -> Result<Self, anyhow::Error> {
+        MaskPattern::from_hex_prefix(hex_prefix).map(Self::from_mask)
+    }
+
+    /// Create a new TargetChecker from hex suffix string
+    /// The suffix is matched against the last hex chars of the ID
+    #[allow(dead_code)]
+    pub fn from_hex_suffix(hex_suffix: &str) -> Result<Self, anyhow::Error> {
+        MaskPattern::empty()
+            .with_suffix(hex_suffix)
+            .map(Self::from_mask)
+    }
+
+    /// Create a TargetChecker from optional prefix and suffix strings ("0x" is stripped)
+    pub fn from_hex_parts(prefix: &str, suffix: &str) -> Result<Self, anyhow::Error> {
+        let prefix = prefix.trim_start_matches("0x");
+        let suffix = suffix.trim_start_matches("0x");
+        if prefix.is_empty() && suffix.is_empty() {
+            anyhow::bail!("Either a prefix or a suffix must be provided");
+        }
+        Self::from_hex_prefix(prefix)?.with_suffix(suffix)
+    }
+
+    /// Create a TargetChecker from a masked pattern
+    ///
+    /// Hex chars are fixed nibbles and `?` is a "don't care" nibble, anchored at the start
+    /// of the ID (`cafe??00`). A single `*` expands to as many `?` as needed to fill the
+    /// 64-char ID, which anchors the rest of the pattern at the end (`cafe*0000`).
+    pub fn from_pattern(pattern: &str) -> Result<Self, anyhow::Error> {
+        MaskPattern::from_pattern(pattern).map(Self::from_mask)
+    }
+
+    /// Create a TargetChecker that matches any of the given hex prefixes
+    pub fn from_prefix_set<S: AsRef<str>>(
+        prefixes: impl IntoIterator<Item = S>,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            kind: TargetKind::Set(Arc::new(PrefixSet::new(prefixes)?)),
+        })
+    }
+
+    /// Create a TargetChecker from a regex over the lowercase `0x…` hex rendering of the ID
+    /// (e.g. `^0x(dead|beef)`)
+    pub fn from_regex(regex: &str) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            kind: TargetKind::Regex(RegexPattern::new(regex)?),
+        })
+    }
+
+    /// Create a TargetChecker for a prefix of the Base58 rendering (e.g. a transaction digest)
+    pub fn from_base58_prefix(prefix: &str, case_insensitive: bool) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            kind: TargetKind::Base58(Base58Prefix::new(prefix, case_insensitive)?),
+        })
+    }
+
+    /// Additionally require the ID to end with the given hex suffix
+    pub fn with_suffix(self, hex_suffix: &str) -> Result<Self, anyhow::Error> {
+        match self.kind {
+            TargetKind::Pattern(mask) => mask.with_suffix(hex_suffix).map(Self::from_mask),
+            _ => anyhow::bail!("A suffix can only be combined with a hex prefix"),
+        }
+    }
+
+    /// Check if the given 32-byte ID matches the target
+    #[inline(always)]
+    pub fn matches(&self, id_bytes: &[u8; 32]) -> bool {
+        match &self.kind {
+            TargetKind::Pattern(mask) => mask.matches(id_bytes),
+            TargetKind::Set(set) => set.find_match(id_bytes).is_some(),
+            TargetKind::Regex(regex) => regex.matches(id_bytes),
+            TargetKind::Base58(prefix) => prefix.matches(id_bytes),
+        }
+    }
+
+    /// Index of the pattern that matched the ID (always 0 for single patterns)
+    #[inline(always)]
+    pub fn find_match(&self, id_bytes: &[u8; 32]) -> Option<usize> {
+        match &self.kind {
+            TargetKind::Pattern(mask) => mask.matches(id_bytes).then_some(0),
+            TargetKind::Set(set) => set.find_match(id_bytes),
+            TargetKind::Regex(regex) => regex.matches(id_bytes).then_some(0),
+            TargetKind::Base58(prefix) => prefix.matches(id_bytes).then_some(0),
+        }
+    }
+
+    /// Human readable form of the pattern at `index` (as returned by `find_match`)
+    pub fn pattern_label(&self, index: usize) -> String {
+        match &self.kind {
+            TargetKind::Pattern(mask) => mask.to_string(),
+            TargetKind::Set(set) => set.pattern(index).to_string(),
+            TargetKind::Regex(regex) => format!("/{}/", regex.source()),
+            TargetKind::Base58(prefix) => prefix.to_string(),
+        }
+    }
+
+    /// Number of patterns in this target
+    pub fn pattern_count(&self) -> usize {
+        match &self.kind {
+            TargetKind::Pattern(_) | TargetKind::Regex(_) | TargetKind::Base58(_) => 1,
+            TargetKind::Set(set) => set.len(),
+        }
+    }
+
+    /// Probability that a single uniformly random ID matches
+    /// None if it cannot be computed (regexes that are not plain literal prefixes)
+    pub fn probability(&self) -> Option<f64> {
+        match &self.kind {
+            TargetKind::Pattern(mask) => Some(16f64.powi(-(mask.fixed_nibbles() as i32))),
+            TargetKind::Set(set) => Some(set.probability()),
+            TargetKind::Regex(regex) => regex.probability(),
+            TargetKind::Base58(prefix) => Some(prefix.probability()),
+        }
+    }
+
+    /// Get the difficulty (number of hex characters to match)
+    /// For other targets this is the equivalent hex prefix length (0 if unknown)
+    pub fn difficulty(&self) -> usize {
+        match &self.kind {
+            TargetKind::Pattern(mask) => mask.fixed_nibbles(),
+            _ => self
+                .probability()
+                .map_or(0, |p| (-p.log(16.0)).round().max(0.0) as usize),
+        }
+    }
+
+    /// Estimate attempts needed (average case, 0 if unknown)
+    pub fn estimated_attempts(&self) -> u64 {
+        match &self.kind {
+            // Each hex char = 4 bits = 16 possibilities
+            TargetKind::Pattern(mask) => 16u64.saturating_pow(mask.fixed_nibbles() as u32),
+            _ => self.probability().map_or(0, |p| (1.0 / p).round() as u64),
+        }
+    }
+
+    /// Whether this target is a plain prefix (the only pattern the GPU kernel supports)
+    #[allow(dead_code)]
+    pub fn is_prefix_only(&self) -> bool {
+        match &self.kind {
+            TargetKind::Pattern(mask) => mask.is_prefix_only(),
+            _ => false,
+        }
+    }
+
+    /// Get the raw prefix bytes for GPU matching
+    #[allow(dead_code)]
+    pub fn prefix_bytes(&self) -> Vec<u8> {
+        match &self.kind {
+            TargetKind::Pattern(mask) => mask.prefix_bytes(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn full_bytes(&self) -> usize {
+        match &self.kind {
+            TargetKind::Pattern(mask) => mask.prefix_len() / 2,
+            _ => 0,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn has_half_byte(&self) -> bool {
+        match &self.kind {
+            TargetKind::Pattern(mask) => mask.prefix_len() % 2 == 1,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for TargetChecker {
+    /// Human readable target, e.g. `0xcafe…beef` or `3 prefixes (0xcafe, 0xdead, 0xbeef)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TargetKind::Pattern(mask) => write!(f, "{}", mask),
+            TargetKind::Set(set) => {
+                let shown: Vec<String> = (0..set.len().min(3))
+                    .map(|i| set.pattern(i).to_string())
+                    .collect();
+                let more = if set.len() > 3 { ", …" } else { "" };
+                write!(f, "{} prefixes ({}{})", set.len(), shown.join(", "), more)
+            }
+            TargetKind::Regex(regex) => write!(f, "regex /{}/", regex.source()),
+            TargetKind::Base58(prefix) => write!(f, "Base58 {}", prefix),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_match() {
+        let checker = TargetChecker::from_hex_prefix("00").unwrap();
+
+        let mut id = [0u8; 32];
+        assert!(checker.matches(&id));
+
+        id[0] = 0x01;
+        assert!(!checker.matches(&id));
+    }
+
+    #[test]
+    fn test_odd_prefix() {
+        let checker = TargetChecker::from_hex_prefix("0").unwrap();
+
+        let mut id = [0u8; 32];
+        id[0] = 0x0F; // High nibble is 0
+        assert!(checker.matches(&id));
+
+        id[0] = 0x10; // High nibble is 1
+        assert!(!checker.matches(&id));
+    }
+
+    #[test]
+    fn test_difficulty_calculation() {
+        let checker1 = TargetChecker::from_hex_prefix("a").unwrap();
+        assert_eq!(checker1.difficulty(), 1);
+
+        let checker2 = TargetChecker::from_hex_prefix("abc").unwrap();
+        assert_eq!(checker2.difficulty(), 3);
+
+        let checker3 = TargetChecker::from_hex_prefix("dead").unwrap();
+        assert_eq!(checker3.difficulty(), 4);
+    }
+
+    #[test]
+    fn test_estimated_attempts() {
+        let checker1 = TargetChecker::from_hex_prefix("0").unwrap();
+        assert_eq!(checker1.estimated_attempts(), 16); // 16^1
+
+        let checker2 = TargetChecker::from_hex_prefix("00").unwrap();
+        assert_eq!(checker2.estimated_attempts(), 256); // 16^2
+
+        let checker3 = TargetChecker::from_hex_prefix("face").unwrap();
+        assert_eq!(checker3.estimated_attempts(), 65536); // 16^4
+    }
+
+    #[test]
+    fn test_longer_prefix() {
+        let checker = TargetChecker::from_hex_prefix("deadbeef").unwrap();
+        assert_eq!(checker.difficulty(), 8);
+
+        // Matching ID
+        let mut id = [0u8; 32];
+        id[0] = 0xde;
+        id[1] = 0xad;
+        id[2] = 0xbe;
+        id[3] = 0xef;
+        assert!(checker.matches(&id));
+
+        // Non-matching ID
+        id[3] = 0xee;
+        assert!(!checker.matches(&id));
+    }
+
+    #[test]
+    fn test_case_handled_properly() {
+        // Prefix should work with uppercase input
+        let checker_upper = TargetChecker::from_hex_prefix("FACE").unwrap();
+        let checker_lower = TargetChecker::from_hex_prefix("face").unwrap();
+
+        let mut id = [0u8; 32];
+        id[0] = 0xfa;
+        id[1] = 0xce;
+
+        // Both should match (hex::decode handles both)
+        assert!(checker_upper.matches(&id));
+        assert!(checker_lower.matches(&id));
+    }
+
+    #[test]
+    fn test_max_length_prefix() {
+        // Full 64 chars (32 bytes)
+        let full_prefix = "00".repeat(32);
+        let checker = TargetChecker::from_hex_prefix(&full_prefix).unwrap();
+        assert_eq!(checker.difficulty(), 64);
+    }
+
+    #[test]
+    fn test_too_long_prefix_rejected() {
+        let too_long = "0".repeat(65);
+        let result = TargetChecker::from_hex_prefix(&too_long);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_suffix_match() {
+        let checker = TargetChecker::from_hex_suffix("beef").unwrap();
+        assert_eq!(checker.difficulty(), 4);
+
+        let mut id = [0u8; 32];
+        id[30] = 0xbe;
+        id[31] = 0xef;
+        assert!(checker.matches(&id));
+
+        id[30] = 0xbf;
+        assert!(!checker.matches(&id));
+    }
+
+    #[test]
+    fn test_odd_suffix() {
+        let checker = TargetChecker::from_hex_suffix("eef").unwrap();
+
+        let mut id = [0u8; 32];
+        id[30] = 0xFE; // Low nibble is e
+        id[31] = 0xef;
+        assert!(checker.matches(&id));
+
+        id[30] = 0xEF; // Low nibble is f
+        assert!(!checker.matches(&id));
+    }
+
+    #[test]
+    fn test_prefix_and_suffix() {
+        let checker = TargetChecker::from_hex_parts("0xcafe", "00").unwrap();
+        assert_eq!(checker.difficulty(), 6);
+        assert_eq!(checker.estimated_attempts(), 16u64.pow(6));
+        assert_eq!(checker.to_string(), "0xcafe…00");
+
+        let mut id = [0xFFu8; 32];
+        id[0] = 0xca;
+        id[1] = 0xfe;
+        assert!(!checker.matches(&id));
+
+        id[31] = 0x00;
+        assert!(checker.matches(&id));
+    }
+
+    #[test]
+    fn test_prefix_plus_suffix_too_long_rejected() {
+        let prefix = "0".repeat(40);
+        let suffix = "0".repeat(25);
+        assert!(TargetChecker::from_hex_parts(&prefix, &suffix).is_err());
+        assert!(TargetChecker::from_hex_parts("", "").is_err());
+    }
+
+    #[test]
+    fn test_masked_pattern() {
+        let checker = TargetChecker::from_pattern("cafe??00").unwrap();
+        assert_eq!(checker.difficulty(), 6);
+        assert_eq!(checker.to_string(), "0xcafe??00");
+
+        let mut id = [0u8; 32];
+        id[0] = 0xca;
+        id[1] = 0xfe;
+        id[2] = 0x12; // Free byte
+        assert!(checker.matches(&id));
+
+        id[2] = 0xab;
+        assert!(checker.matches(&id));
+
+        id[3] = 0x01;
+        assert!(!checker.matches(&id));
+    }
+
+    #[test]
+    fn test_pattern_with_gap() {
+        let checker = TargetChecker::from_pattern("0xcafe*0000").unwrap();
+        assert_eq!(checker.difficulty(), 8);
+        assert_eq!(checker.to_string(), "0xcafe…0000");
+        assert!(!checker.is_prefix_only());
+
+        let mut id = [0x55u8; 32];
+        id[0] = 0xca;
+        id[1] = 0xfe;
+        id[30] = 0x00;
+        id[31] = 0x00;
+        assert!(checker.matches(&id));
+
+        id[31] = 0x10;
+        assert!(!checker.matches(&id));
+    }
+
+    #[test]
+    fn test_pattern_odd_nibble_positions() {
+        // Fixed low nibble of byte 0 and high nibble of byte 1
+        let checker = TargetChecker::from_pattern("?ab").unwrap();
+        assert_eq!(checker.difficulty(), 2);
+
+        let mut id = [0u8; 32];
+        id[0] = 0xfa;
+        id[1] = 0xb3;
+        assert!(checker.matches(&id));
+
+        id[0] = 0xfb;
+        assert!(!checker.matches(&id));
+    }
+
+    #[test]
+    fn test_prefix_pattern_is_gpu_compatible() {
+        let checker = TargetChecker::from_pattern("abc").unwrap();
+        assert!(checker.is_prefix_only());
+        assert_eq!(checker.prefix_bytes(), vec![0xab, 0xc0]);
+        assert_eq!(checker.full_bytes(), 1);
+        assert!(checker.has_half_byte());
+    }
+
+    #[test]
+    fn test_invalid_patterns_rejected() {
+        assert!(TargetChecker::from_pattern("ca*fe*").is_err());
+        assert!(TargetChecker::from_pattern("????").is_err());
+        assert!(TargetChecker::from_pattern("cafg").is_err());
+        assert!(TargetChecker::from_pattern(&"0".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_prefix_set_reports_matched_pattern() {
+        let checker = TargetChecker::from_prefix_set(["cafe", "dead", "beef"]).unwrap();
+        assert_eq!(checker.pattern_count(), 3);
+        assert!(!checker.is_prefix_only());
+        assert_eq!(checker.to_string(), "3 prefixes (0xcafe, 0xdead, 0xbeef)");
+
+        let mut id = [0u8; 32];
+        id[0] = 0xbe;
+        id[1] = 0xef;
+        assert!(checker.matches(&id));
+        let index = checker.find_match(&id).unwrap();
+        assert_eq!(checker.pattern_label(index), "0xbeef");
+
+        id[1] = 0xee;
+        assert_eq!(checker.find_match(&id), None);
+    }
+
+    #[test]
+    fn test_prefix_set_difficulty() {
+        // 16 four-char prefixes are as likely as one three-char prefix
+        let prefixes: Vec<String> = (0..16).map(|i| format!("abc{:x}", i)).collect();
+        let checker = TargetChecker::from_prefix_set(&prefixes).unwrap();
+        assert_eq!(checker.difficulty(), 3);
+        assert_eq!(checker.estimated_attempts(), 4096);
+    }
+
+    #[test]
+    fn test_regex_target() {
+        let checker = TargetChecker::from_regex("^0x(dead|beef)").unwrap();
+        assert_eq!(checker.difficulty(), 4);
+        assert_eq!(checker.to_string(), "regex /^0x(dead|beef)/");
+
+        let mut id = [0u8; 32];
+        id[0] = 0xbe;
+        id[1] = 0xef;
+        assert_eq!(checker.find_match(&id), Some(0));
+
+        let unknown = TargetChecker::from_regex("(00){3}").unwrap();
+        assert_eq!(unknown.probability(), None);
+        assert_eq!(unknown.estimated_attempts(), 0);
+    }
+
+    #[test]
+    fn test_invalid_hex_rejected() {
+        let result = TargetChecker::from_hex_prefix("xyz");
+        assert!(result.is_err());
+    }
+}
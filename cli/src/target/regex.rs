@@ -0,0 +1,325 @@
+//! Regex targets - match a regular expression against the `0x…` hex rendering of the ID
+
+use super::set::PrefixSet;
+use anyhow::Context;
+use regex::bytes::Regex;
+use regex_syntax::ast::ErrorKind;
+use regex_syntax::hir::Look;
+use regex_syntax::hir::literal::{ExtractKind, Extractor};
+
+/// Length of the rendered ID: "0x" + 64 lowercase hex chars
+const RENDERED_LEN: usize = 66;
+
+/// A regex over the lowercase hex rendering of a 32-byte ID (`0x` + 64 hex chars)
+///
+/// When the regex is anchored at the start, its literal prefixes are compiled into a
+/// `PrefixSet` that rejects almost every ID on the raw bytes, before any hex encoding or
+/// regex execution happens. Regexes with backreferences (e.g. `(.)\1{7}`) run on a
+/// backtracking engine behind the same prefilter.
+#[derive(Debug, Clone)]
+pub struct RegexPattern {
+    source: String,
+    regex: Matcher,
+    /// Every match must start with one of these hex prefixes (None = no cheap filter)
+    prefilter: Option<PrefixSet>,
+    /// Probability of a match, if the regex is exactly its literal prefixes
+    probability: Option<f64>,
+}
+
+/// Engine a regex runs on
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// The `regex` crate, for everything it supports
+    Automaton(Regex),
+    /// `fancy-regex`, for backreferences
+    Backtracking(fancy_regex::Regex),
+}
+
+impl RegexPattern {
+    pub fn new(source: &str) -> anyhow::Result<Self> {
+        let invalid = || format!("Invalid regex '{}'", source);
+        let (regex, analyzed) = if has_backreference(source) {
+            let regex = fancy_regex::Regex::new(source).with_context(invalid)?;
+            // A backreference matches some text, so a wildcard in its place only widens the
+            // language and the literal prefixes still filter soundly
+            (Matcher::Backtracking(regex), widen_backreferences(source))
+        } else {
+            let regex = Regex::new(source).with_context(invalid)?;
+            (Matcher::Automaton(regex), source.to_string())
+        };
+        let hir = regex_syntax::parse(&analyzed).with_context(invalid)?;
+
+        let anchored = hir.properties().look_set_prefix().contains(Look::Start);
+        let seq = Extractor::new().kind(ExtractKind::Prefix).extract(&hir);
+
+        let (prefilter, exact) = match seq.literals() {
+            Some(literals) if anchored => {
+                let mut prefixes = Vec::new();
+                let mut exact = true;
+                let mut unconstrained = false;
+
+                for literal in literals {
+                    match hex_prefix_of(literal.as_bytes()) {
+                        // Can never match a lowercase "0x…" string
+                        None => continue,
+                        Some((hex, truncated)) => {
+                            if hex.is_empty() {
+                                unconstrained = true;
+                            }
+                            exact &= literal.is_exact() && !truncated;
+                            prefixes.push(hex);
+                        }
+                    }
+                }
+
+                if prefixes.is_empty() {
+                    anyhow::bail!(
+                        "Regex '{}' can never match a lowercase 0x-prefixed hex ID",
+                        source
+                    );
+                }
+
+                if unconstrained {
+                    (None, false)
+                } else {
+                    (Some(PrefixSet::new(&prefixes)?), exact)
+                }
+            }
+            _ => (None, false),
+        };
+
+        // Any other assertion (e.g. `$` or `\b`) makes the literal estimate unreliable
+        let only_start_anchor = hir.properties().look_set().remove(Look::Start).is_empty();
+        let backtracking = matches!(regex, Matcher::Backtracking(_));
+        let probability = match &prefilter {
+            Some(set) if exact && only_start_anchor && !backtracking => Some(set.probability()),
+            _ => None,
+        };
+
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+            prefilter,
+            probability,
+        })
+    }
+
+    #[inline(always)]
+    pub fn matches(&self, id_bytes: &[u8; 32]) -> bool {
+        if let Some(prefilter) = &self.prefilter
+            && prefilter.find_match(id_bytes).is_none()
+        {
+            return false;
+        }
+
+        let mut rendered = [0u8; RENDERED_LEN];
+        rendered[..2].copy_from_slice(b"0x");
+        hex::encode_to_slice(id_bytes, &mut rendered[2..]).expect("buffer has room for 64 chars");
+        match &self.regex {
+            Matcher::Automaton(regex) => regex.is_match(&rendered),
+            Matcher::Backtracking(regex) => {
+                let rendered = std::str::from_utf8(&rendered).expect("hex is ASCII");
+                // Only a blown backtracking limit errors, which counts as no match
+                regex.is_match(rendered).unwrap_or(false)
+            }
+        }
+    }
+
+    /// Probability that a random ID matches, when it can be computed from the regex literals
+    pub fn probability(&self) -> Option<f64> {
+        self.probability
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+/// Convert a literal prefix of the rendered ID into a hex prefix of the raw ID bytes
+///
+/// Returns None if no rendered ID can start with the literal, otherwise the hex chars after
+/// "0x" (possibly empty) and whether they had to be truncated to 64 chars.
+fn hex_prefix_of(literal: &[u8]) -> Option<(String, bool)> {
+    // The literal and "0x" must agree on their common length
+    let head = literal.len().min(2);
+    if literal[..head] != b"0x"[..head] {
+        return None;
+    }
+
+    let hex = &literal[head..];
+    if !hex.iter().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return None;
+    }
+
+    let truncated = hex.len() > RENDERED_LEN - 2;
+    let hex = &hex[..hex.len().min(RENDERED_LEN - 2)];
+    Some((String::from_utf8(hex.to_vec()).ok()?, truncated))
+}
+
+/// Whether the regex uses a backreference, which the `regex` crate doesn't support
+fn has_backreference(source: &str) -> bool {
+    match regex_syntax::ast::parse::Parser::new().parse(source) {
+        Err(e) => matches!(e.kind(), ErrorKind::UnsupportedBackreference),
+        Ok(_) => false,
+    }
+}
+
+/// Replace every backreference (`\1`, `\k<name>`, ...) with `(?s:.*)`
+///
+/// Escaped backslashes are skipped, so `\\1` stays a literal backslash followed by "1".
+fn widen_backreferences(source: &str) -> String {
+    let mut widened = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            widened.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some(d) if d.is_ascii_digit() => {
+                while chars.next_if(char::is_ascii_digit).is_some() {}
+                widened.push_str("(?s:.*)");
+            }
+            Some('k') => {
+                chars.next();
+                let close = match chars.next() {
+                    Some('<') => '>',
+                    Some('{') => '}',
+                    Some('\'') => '\'',
+                    other => {
+                        widened.push_str("\\k");
+                        widened.extend(other);
+                        continue;
+                    }
+                };
+                while chars.next().is_some_and(|c| c != close) {}
+                widened.push_str("(?s:.*)");
+            }
+            Some(_) => {
+                widened.push(c);
+                widened.extend(chars.next());
+            }
+            None => widened.push(c),
+        }
+    }
+    widened
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id_with(leading: &[u8]) -> [u8; 32] {
+        let mut id = [0x77u8; 32];
+        id[..leading.len()].copy_from_slice(leading);
+        id
+    }
+
+    #[test]
+    fn test_backreference_run() {
+        let pattern = RegexPattern::new(r"(.)\1{7}").unwrap();
+        assert!(pattern.prefilter.is_none());
+        assert_eq!(pattern.probability(), None);
+
+        // "5555 5555" across bytes 10..14
+        let mut id = [0u8; 32];
+        for (i, byte) in id.iter_mut().enumerate() {
+            *byte = (i as u8).wrapping_mul(37);
+        }
+        assert!(!pattern.matches(&id));
+        id[10..14].copy_from_slice(&[0x55; 4]);
+        assert!(pattern.matches(&id));
+    }
+
+    #[test]
+    fn test_backreference_keeps_prefilter() {
+        let pattern = RegexPattern::new(r"^0xcafe(.)\1{3}").unwrap();
+        let prefilter = pattern.prefilter.as_ref().unwrap();
+        assert!(
+            prefilter
+                .find_match(&id_with(&[0xca, 0xfe, 0x12]))
+                .is_some()
+        );
+        assert_eq!(pattern.probability(), None);
+
+        assert!(pattern.matches(&id_with(&[0xca, 0xfe, 0x99, 0x99])));
+        assert!(!pattern.matches(&id_with(&[0xca, 0xfe, 0x99, 0x98])));
+        assert!(!pattern.matches(&id_with(&[0xbe, 0xef, 0x99, 0x99])));
+
+        assert_eq!(
+            widen_backreferences(r"(?<a>.)\k<a>\\1\12"),
+            r"(?<a>.)(?s:.*)\\1(?s:.*)"
+        );
+    }
+
+    #[test]
+    fn test_anchored_alternation() {
+        let pattern = RegexPattern::new("^0x(dead|beef)").unwrap();
+        assert!(pattern.prefilter.is_some());
+
+        assert!(pattern.matches(&id_with(&[0xde, 0xad])));
+        assert!(pattern.matches(&id_with(&[0xbe, 0xef])));
+        assert!(!pattern.matches(&id_with(&[0xca, 0xfe])));
+
+        let probability = pattern.probability().unwrap();
+        assert!((probability - 2.0 / 65536.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_unanchored_regex_has_no_estimate() {
+        let pattern = RegexPattern::new("beef").unwrap();
+        assert!(pattern.prefilter.is_none());
+        assert_eq!(pattern.probability(), None);
+
+        let mut id = [0u8; 32];
+        id[10] = 0xbe;
+        id[11] = 0xef;
+        assert!(pattern.matches(&id));
+    }
+
+    #[test]
+    fn test_inexact_prefix_still_filters() {
+        // Literal prefix "0xca" followed by a large class: filtered but not estimated
+        let pattern = RegexPattern::new("^0xca[0-9a-f]{2}").unwrap();
+        assert!(pattern.prefilter.is_some());
+        assert_eq!(pattern.probability(), None);
+
+        assert!(pattern.matches(&id_with(&[0xca, 0x12])));
+        assert!(!pattern.matches(&id_with(&[0xcb, 0x12])));
+    }
+
+    #[test]
+    fn test_small_class_is_expanded() {
+        // [0-9]{2} expands to 100 exact literals
+        let pattern = RegexPattern::new("^0xca[0-9]{2}").unwrap();
+        let probability = pattern.probability().unwrap();
+        assert!((probability - 100.0 / 65536.0).abs() < 1e-12);
+
+        assert!(pattern.matches(&id_with(&[0xca, 0x12])));
+        assert!(!pattern.matches(&id_with(&[0xca, 0xfe])));
+    }
+
+    #[test]
+    fn test_end_anchor_disables_estimate() {
+        let pattern = RegexPattern::new("^0xdead.*0$").unwrap();
+        assert_eq!(pattern.probability(), None);
+
+        let mut id = id_with(&[0xde, 0xad]);
+        id[31] = 0x10;
+        assert!(pattern.matches(&id));
+    }
+
+    #[test]
+    fn test_impossible_regex_rejected() {
+        assert!(RegexPattern::new("^0xDEAD").is_err());
+        assert!(RegexPattern::new("^1x").is_err());
+        assert!(RegexPattern::new("(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_case_insensitive_regex() {
+        let pattern = RegexPattern::new("(?i)^0xDEAD").unwrap();
+        assert!(pattern.matches(&id_with(&[0xde, 0xad])));
+        assert!(!pattern.matches(&id_with(&[0xde, 0xae])));
+    }
+}
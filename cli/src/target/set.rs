@@ -0,0 +1,171 @@
+//! Prefix sets - match an ID against many hex prefixes with a single table lookup
+
+use super::mask::MaskPattern;
+use anyhow::Context;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+/// Max number of leading nibbles used as lookup key (16^4 = 65536 buckets)
+const MAX_KEY_NIBBLES: usize = 4;
+
+/// A set of hex prefixes indexed by their leading nibbles
+///
+/// Each bucket lists the prefixes compatible with one value of the first `key_nibbles`
+/// nibbles, so a check costs one table lookup plus (usually) zero or one comparison.
+/// Prefixes shorter than the key are expanded into every bucket they cover.
+#[derive(Debug, Clone)]
+pub struct PrefixSet {
+    patterns: Vec<MaskPattern>,
+    key_nibbles: usize,
+    /// Pattern indices per key, longest (rarest) pattern first
+    buckets: Vec<Vec<u32>>,
+}
+
+impl PrefixSet {
+    /// Build a set from hex prefixes ("0x" is stripped, duplicates are ignored)
+    pub fn new<S: AsRef<str>>(prefixes: impl IntoIterator<Item = S>) -> anyhow::Result<Self> {
+        let mut patterns = Vec::new();
+        let mut seen = HashSet::new();
+
+        for prefix in prefixes {
+            let prefix = prefix
+                .as_ref()
+                .trim()
+                .trim_start_matches("0x")
+                .to_ascii_lowercase();
+            if prefix.is_empty() {
+                anyhow::bail!("Empty prefix in target set");
+            }
+            if !seen.insert(prefix.clone()) {
+                continue;
+            }
+            let pattern = MaskPattern::from_hex_prefix(&prefix)
+                .with_context(|| format!("Invalid prefix '{}' in target set", prefix))?;
+            patterns.push(pattern);
+        }
+
+        if patterns.is_empty() {
+            anyhow::bail!("Target set must contain at least one prefix");
+        }
+
+        let max_len = patterns.iter().map(|p| p.fixed_nibbles()).max().unwrap();
+        let key_nibbles = max_len.min(MAX_KEY_NIBBLES);
+
+        let mut buckets = vec![Vec::new(); 1 << (4 * key_nibbles)];
+        for (index, pattern) in patterns.iter().enumerate() {
+            // Leading nibbles fixed by the prefix, the remaining key nibbles take every value
+            let fixed = pattern.fixed_nibbles().min(key_nibbles);
+            let base = (0..fixed).fold(0usize, |key, pos| {
+                (key << 4) | pattern.nibble(pos).unwrap() as usize
+            });
+            let free_bits = 4 * (key_nibbles - fixed);
+            for free in 0..(1usize << free_bits) {
+                buckets[(base << free_bits) | free].push(index as u32);
+            }
+        }
+        for bucket in &mut buckets {
+            bucket.sort_by_key(|&i| Reverse(patterns[i as usize].fixed_nibbles()));
+        }
+
+        Ok(Self {
+            patterns,
+            key_nibbles,
+            buckets,
+        })
+    }
+
+    /// Lookup key: the first `key_nibbles` nibbles of the ID
+    #[inline(always)]
+    fn key(&self, id_bytes: &[u8; 32]) -> usize {
+        let leading = u16::from_be_bytes([id_bytes[0], id_bytes[1]]) as usize;
+        leading >> (16 - 4 * self.key_nibbles)
+    }
+
+    /// Index of the longest prefix matching the ID, if any
+    #[inline(always)]
+    pub fn find_match(&self, id_bytes: &[u8; 32]) -> Option<usize> {
+        self.buckets[self.key(id_bytes)]
+            .iter()
+            .map(|&i| i as usize)
+            .find(|&i| self.patterns[i].matches(id_bytes))
+    }
+
+    /// Number of distinct prefixes
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    pub fn pattern(&self, index: usize) -> &MaskPattern {
+        &self.patterns[index]
+    }
+
+    /// Probability that a uniformly random ID matches at least one prefix
+    pub fn probability(&self) -> f64 {
+        // A prefix that extends a shorter prefix in the set adds nothing to the union
+        self.patterns
+            .iter()
+            .filter(|p| !self.patterns.iter().any(|q| extends(p, q)))
+            .map(|p| 16f64.powi(-(p.fixed_nibbles() as i32)))
+            .sum()
+    }
+}
+
+/// Whether prefix `longer` starts with the (strictly shorter) prefix `shorter`
+fn extends(longer: &MaskPattern, shorter: &MaskPattern) -> bool {
+    shorter.fixed_nibbles() < longer.fixed_nibbles()
+        && (0..shorter.fixed_nibbles()).all(|pos| shorter.nibble(pos) == longer.nibble(pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id_with(leading: &[u8]) -> [u8; 32] {
+        let mut id = [0x77u8; 32];
+        id[..leading.len()].copy_from_slice(leading);
+        id
+    }
+
+    #[test]
+    fn test_finds_matching_prefix() {
+        let set = PrefixSet::new(["cafe", "dead", "beef"]).unwrap();
+        assert_eq!(set.len(), 3);
+
+        assert_eq!(set.find_match(&id_with(&[0xde, 0xad])), Some(1));
+        assert_eq!(set.find_match(&id_with(&[0xbe, 0xef])), Some(2));
+        assert_eq!(set.find_match(&id_with(&[0xca, 0xfa])), None);
+    }
+
+    #[test]
+    fn test_mixed_lengths_prefer_longest() {
+        let set = PrefixSet::new(["a", "abc", "abcdef"]).unwrap();
+
+        assert_eq!(set.find_match(&id_with(&[0xab, 0xcd, 0xef])), Some(2));
+        assert_eq!(set.find_match(&id_with(&[0xab, 0xc0])), Some(1));
+        assert_eq!(set.find_match(&id_with(&[0xa1])), Some(0));
+        assert_eq!(set.find_match(&id_with(&[0xb1])), None);
+    }
+
+    #[test]
+    fn test_duplicates_and_case_ignored() {
+        let set = PrefixSet::new(["CAFE", "0xcafe", "cafe"]).unwrap();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_probability_of_union() {
+        let set = PrefixSet::new(["00", "11"]).unwrap();
+        assert!((set.probability() - 2.0 / 256.0).abs() < 1e-12);
+
+        // "001" is already covered by "00"
+        let set = PrefixSet::new(["00", "001"]).unwrap();
+        assert!((set.probability() - 1.0 / 256.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_entries_rejected() {
+        assert!(PrefixSet::new(["cafe", "xyz"]).is_err());
+        assert!(PrefixSet::new(["cafe", ""]).is_err());
+        assert!(PrefixSet::new(Vec::<String>::new()).is_err());
+    }
+}